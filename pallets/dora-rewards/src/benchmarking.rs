@@ -8,15 +8,19 @@ use super::*;
 use crate::Pallet as DoraRewards;
 use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, vec};
 use frame_system::RawOrigin;
+use sp_core::H160;
+use sp_std::vec::Vec;
 
 const SEED: u32 = 0;
 
 benchmarks! {
     initialize_contributors_list {
-        let alice: T::AccountId = account("alice", 0, SEED);
-        let bob: T::AccountId = account("bob", 0, SEED);
-        let list = vec![(alice, 100u32.into()), (bob, 300u32.into())];
+        let c in 1 .. T::MaxContributorsNumber::get();
+        let list: Vec<(T::AccountId, BalanceOf<T>)> = (0 .. c).map(|i| (account("contributor", i, SEED), 100u32.into())).collect();
     }: _(RawOrigin::Root, list)
+    verify {
+        assert_eq!(DoraRewards::<T>::total_contributors(), c);
+    }
 
     complete_initialization {
         let alice: T::AccountId = account("alice", 0, SEED);
@@ -32,6 +36,12 @@ benchmarks! {
         let _ = DoraRewards::<T>::initialize_contributors_list(<T as frame_system::Config>::Origin::from(RawOrigin::Root), list);
         let _ = DoraRewards::<T>::complete_initialization(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 300u32.into());
     }: _(RawOrigin::Signed(alice))
+
+    register_eth_address {
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let list = vec![(alice.clone(), 100u32.into())];
+        let _ = DoraRewards::<T>::initialize_contributors_list(<T as frame_system::Config>::Origin::from(RawOrigin::Root), list);
+    }: _(RawOrigin::Signed(alice), H160::repeat_byte(1))
 }
 
 impl_benchmark_test_suite!(DoraRewards, crate::mock::new_test_ext(), crate::mock::Test,);
@@ -52,6 +52,7 @@
 
 pub use pallet::*;
 mod benchmarking;
+pub mod migrations;
 #[cfg(test)]
 pub(crate) mod mock;
 #[cfg(test)]
@@ -82,11 +83,15 @@ pub mod pallet {
     // DoraFactory Crowdloan rewards pallet
     #[pallet::pallet]
     #[pallet::without_storage_info]
+    #[pallet::storage_version(STORAGE_VERSION)]
     // The crowdloan rewards pallet
     pub struct Pallet<T>(PhantomData<T>);
 
     pub const PALLET_ID: PalletId = PalletId(*b"DoraRewa");
 
+    /// Version of the layout of the storage, upgraded by the migrations
+    pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::config]
     pub trait Config: frame_system::Config {
         /// The overarching event type
@@ -145,7 +150,7 @@ pub mod pallet {
     /// Record contributor's info (total reward, claimed reward, track block number)
     #[pallet::storage]
     #[pallet::getter(fn rewards_info)]
-    type ContributorsInfo<T: Config> =
+    pub(super) type ContributorsInfo<T: Config> =
         StorageMap<_, Blake2_128Concat, T::AccountId, RewardInfo<T>, OptionQuery>;
 
     #[pallet::storage]
@@ -263,9 +268,11 @@ pub mod pallet {
                 .clone()
                 .saturating_sub(contribute_info.track_block_number.clone());
             // compute the linear reward by the linear block period
-            let current_linear_reward = left_linear_reward
-                .saturating_mul(curr_linear_reward_period.into())
-                / total_reward_period.into();
+            let current_linear_reward = primitives::vesting::linear_release(
+                left_linear_reward,
+                curr_linear_reward_period.into(),
+                total_reward_period.into(),
+            );
 
             // Get the comming reward
             let coming_reward = if contribute_info.claimed_reward == 0u32.into() {
@@ -318,7 +325,7 @@ pub mod pallet {
 
         ///  Initialize contributor's rewards info which is a contributors vec
         ///  this operation should be execute by sudo user
-        #[pallet::weight(T::WeightInfo::initialize_contributors_list())]
+        #[pallet::weight(T::WeightInfo::initialize_contributors_list(contributor_list.len() as u32))]
         pub fn initialize_contributors_list(
             origin: OriginFor<T>,
             contributor_list: Vec<(T::AccountId, BalanceOf<T>)>,
@@ -385,7 +392,7 @@ pub mod pallet {
             Ok(().into())
         }
 
-        #[pallet::weight(100)]
+        #[pallet::weight(T::WeightInfo::register_eth_address())]
         pub fn register_eth_address(origin: OriginFor<T>, eth_address: H160) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
 
@@ -0,0 +1,51 @@
+//! Migrations of the storage of the pallet, to be added to the `Executive` of the runtimes.
+
+use crate::*;
+use frame_support::{
+    traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+    weights::Weight,
+};
+use sp_std::marker::PhantomData;
+
+/// From the unversioned first release to version 1.
+pub mod v1 {
+    use super::*;
+
+    /// The layout of [`RewardInfo`] did not change, only the version is recorded so that later
+    /// changes can be migrated from it.
+    pub struct MigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if Pallet::<T>::on_chain_storage_version() >= 1 {
+                return T::DbWeight::get().reads(1);
+            }
+            StorageVersion::new(1).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(1, 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<(), &'static str> {
+            frame_support::ensure!(
+                Pallet::<T>::on_chain_storage_version() == 0,
+                "DoraRewards is already at version 1"
+            );
+            Ok(())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade() -> Result<(), &'static str> {
+            frame_support::ensure!(
+                Pallet::<T>::on_chain_storage_version() == 1,
+                "DoraRewards was not migrated to version 1"
+            );
+            // every contributor still decodes with the current layout
+            frame_support::ensure!(
+                ContributorsInfo::<T>::iter_keys()
+                    .all(|contributor| ContributorsInfo::<T>::get(contributor).is_some()),
+                "contributors which do not decode"
+            );
+            Ok(())
+        }
+    }
+}
@@ -296,6 +296,33 @@ fn claim_reward_step_by_step() {
     })
 }
 
+/// claiming for the first time at or after the end of the vesting releases the whole reward
+#[test]
+fn claim_whole_reward_at_the_end_of_vesting() {
+    empty().execute_with(|| {
+        roll_to(2);
+        let init_block = DoraRewards::init_vesting_block();
+        assert_ok!(DoraRewards::initialize_contributors_list(
+            Origin::root(),
+            vec![(1, 330u32.into()), (4, 400u32.into())]
+        ));
+        assert_ok!(DoraRewards::complete_initialization(
+            Origin::root(),
+            init_block + VESTING
+        ));
+
+        // 240 + 960 * ((10 - 2) / 8) = 1200
+        roll_to(10);
+        assert_ok!(DoraRewards::claim_rewards(Origin::signed(4)));
+        assert_eq!(DoraRewards::rewards_info(&4).unwrap().claimed_reward, 1200);
+
+        // the blocks after the end are not counted
+        roll_to(15);
+        assert_ok!(DoraRewards::claim_rewards(Origin::signed(1)));
+        assert_eq!(DoraRewards::rewards_info(&1).unwrap().claimed_reward, 990);
+    })
+}
+
 #[test]
 fn floating_point_arithmetic_works() {
     empty().execute_with(|| {
@@ -507,4 +534,40 @@ fn dora_rewards_re_register_eth_addr_works_tests() {
             Error::<Test>::NotInContributorList
         );
     });
-}
\ No newline at end of file
+}
+
+/// the unversioned storage is upgraded to version 1 once, leaving the contributors as they are
+#[test]
+fn migrate_to_v1_records_the_version() {
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+    empty().execute_with(|| {
+        StorageVersion::new(0).put::<DoraRewards>();
+        assert_ok!(DoraRewards::initialize_contributors_list(
+            Origin::root(),
+            vec![(1, 100u32.into())]
+        ));
+        let reward_info = DoraRewards::rewards_info(&1);
+
+        for _ in 0..2 {
+            migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+            assert_eq!(DoraRewards::on_chain_storage_version(), 1);
+            assert_eq!(DoraRewards::rewards_info(&1), reward_info);
+        }
+    });
+}
+
+/// the weight of the contributor list initialization grows with the length of the list
+#[test]
+fn initialize_contributors_list_weight_scales_with_list() {
+    use frame_support::dispatch::GetDispatchInfo;
+    empty().execute_with(|| {
+        let call = |len: u64| crate::Call::<Test>::initialize_contributors_list {
+            contributor_list: (0..len).map(|who| (who, 100u32.into())).collect(),
+        };
+        assert_eq!(
+            call(3).get_dispatch_info().weight,
+            <() as WeightInfo>::initialize_contributors_list(3)
+        );
+        assert!(call(3).get_dispatch_info().weight > call(1).get_dispatch_info().weight);
+    });
+}
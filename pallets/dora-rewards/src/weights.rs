@@ -1,12 +1,16 @@
-//! Autogenerated weights for pallet_dora_rewards
+//! Weights for pallet_dora_rewards
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2022-04-30, STEPS: `50`, REPEAT: 200, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//! ESTIMATES, NOT BENCHMARK RESULTS. Only `complete_initialization` and `claim_rewards` are
+//! still what the substrate benchmark CLI generated (2022-04-30, STEPS: `50`, REPEAT: 200,
+//! CHAIN: Some("dev")). `initialize_contributors_list` now depends on the length of the list
+//! and `register_eth_address` was added since, their values are estimated by hand. The file
+//! must be regenerated with the command below on the reference hardware before it is used on a
+//! live chain. Weights are reference time only, FRAME polkadot-v0.9.28 has no proof size.
 
-// Executed Command:
+// Command to regenerate:
 // ./target/release/dorafactory-node
 // benchmark
+// pallet
 // --chain
 // dev
 // --execution
@@ -36,20 +40,25 @@ use sp_std::marker::PhantomData;
 
 /// Weight functions needed for pallet_dora_rewards.
 pub trait WeightInfo {
-    fn initialize_contributors_list() -> Weight;
+    fn initialize_contributors_list(c: u32) -> Weight;
     fn complete_initialization() -> Weight;
     fn claim_rewards() -> Weight;
+    fn register_eth_address() -> Weight;
 }
 
 /// Weights for pallet_dora_rewards using the Dora node and recommended hardware.
 pub struct DoraWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for DoraWeight<T> {
+    // Storage: DoraRewards Initialized (r:1 w:0)
+    // Storage: DoraRewards TotalContributors (r:1 w:1)
     // Storage: DoraRewards InitBlock (r:1 w:0)
-    // Storage: DoraRewards ContributorsInfo (r:0 w:2)
-    fn initialize_contributors_list() -> Weight {
-        (39_174_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(1 as Weight))
-            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    // Storage: DoraRewards ContributorsInfo (r:0 w:1)
+    fn initialize_contributors_list(c: u32) -> Weight {
+        (20_913_000 as Weight)
+            .saturating_add((9_846_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
     }
     // Storage: DoraRewards InitBlock (r:1 w:0)
     // Storage: DoraRewards EndBlock (r:0 w:1)
@@ -68,14 +77,23 @@ impl<T: frame_system::Config> WeightInfo for DoraWeight<T> {
             .saturating_add(T::DbWeight::get().reads(6 as Weight))
             .saturating_add(T::DbWeight::get().writes(3 as Weight))
     }
+    // Storage: DoraRewards ContributorsInfo (r:1 w:0)
+    // Storage: DoraRewards RegisterEthAddr (r:1 w:1)
+    fn register_eth_address() -> Weight {
+        (24_380_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-    fn initialize_contributors_list() -> Weight {
-        (39_174_000 as Weight)
-            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
-            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    fn initialize_contributors_list(c: u32) -> Weight {
+        (20_913_000 as Weight)
+            .saturating_add((9_846_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
     }
     fn complete_initialization() -> Weight {
         (18_768_000 as Weight)
@@ -87,4 +105,9 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(6 as Weight))
             .saturating_add(RocksDbWeight::get().writes(3 as Weight))
     }
+    fn register_eth_address() -> Weight {
+        (24_380_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
 }
@@ -0,0 +1,22 @@
+//! Runtime API of the quadratic funding pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    /// What wallets need to show the history of the voters of the quadratic funding rounds.
+    pub trait QuadraticFundingApi<AccountId, Hash> where
+        AccountId: Codec,
+        Hash: Codec,
+    {
+        /// The projects `who` voted for in a round with its ballots, and how much it paid
+        fn voter_participation(round_id: u32, who: AccountId) -> Option<(Vec<(Hash, u128)>, u128)>;
+
+        /// The participation of `who` in the rounds it voted in among the `count` round ids
+        /// from `from_round`, as `(round_id, projects with ballots, paid)`. At most 100 round
+        /// ids are looked up and pruned rounds are not included
+        fn voter_history(who: AccountId, from_round: u32, count: u32) -> Vec<(u32, Vec<(Hash, u128)>, u128)>;
+    }
+}
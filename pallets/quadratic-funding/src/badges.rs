@@ -0,0 +1,79 @@
+//! `pallet_uniques` as the non-fungible tokens of the participation badges.
+//!
+//! The badges keep their attributes on their item, where wallets and indexers look for them.
+//! `pallet_uniques` only sets attributes through its `set_attribute` call, [`UniquesBadges`]
+//! calls it with the root origin, which the `ForceOrigin` of `pallet_uniques` has to accept.
+
+use frame_support::{
+    dispatch::DispatchResult,
+    traits::tokens::nonfungibles::{Create, Inspect, Mutate},
+};
+use frame_system::RawOrigin;
+use sp_runtime::TokenError;
+use sp_std::{marker::PhantomData, vec::Vec};
+
+type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
+
+/// The items of an instance of `pallet_uniques`, with the attributes set by its root origin.
+pub struct UniquesBadges<T, I = ()>(PhantomData<(T, I)>);
+
+impl<T: pallet_uniques::Config<I>, I: 'static> Inspect<AccountIdOf<T>> for UniquesBadges<T, I> {
+    type ItemId = T::ItemId;
+    type CollectionId = T::CollectionId;
+
+    fn owner(collection: &T::CollectionId, item: &T::ItemId) -> Option<AccountIdOf<T>> {
+        <pallet_uniques::Pallet<T, I> as Inspect<_>>::owner(collection, item)
+    }
+
+    fn collection_owner(collection: &T::CollectionId) -> Option<AccountIdOf<T>> {
+        <pallet_uniques::Pallet<T, I> as Inspect<_>>::collection_owner(collection)
+    }
+
+    fn attribute(collection: &T::CollectionId, item: &T::ItemId, key: &[u8]) -> Option<Vec<u8>> {
+        <pallet_uniques::Pallet<T, I> as Inspect<_>>::attribute(collection, item, key)
+    }
+
+    fn can_transfer(collection: &T::CollectionId, item: &T::ItemId) -> bool {
+        <pallet_uniques::Pallet<T, I> as Inspect<_>>::can_transfer(collection, item)
+    }
+}
+
+impl<T: pallet_uniques::Config<I>, I: 'static> Create<AccountIdOf<T>> for UniquesBadges<T, I> {
+    fn create_collection(
+        collection: &T::CollectionId,
+        who: &AccountIdOf<T>,
+        admin: &AccountIdOf<T>,
+    ) -> DispatchResult {
+        <pallet_uniques::Pallet<T, I> as Create<_>>::create_collection(collection, who, admin)
+    }
+}
+
+impl<T: pallet_uniques::Config<I>, I: 'static> Mutate<AccountIdOf<T>> for UniquesBadges<T, I> {
+    fn mint_into(
+        collection: &T::CollectionId,
+        item: &T::ItemId,
+        who: &AccountIdOf<T>,
+    ) -> DispatchResult {
+        <pallet_uniques::Pallet<T, I> as Mutate<_>>::mint_into(collection, item, who)
+    }
+
+    fn set_attribute(
+        collection: &T::CollectionId,
+        item: &T::ItemId,
+        key: &[u8],
+        value: &[u8],
+    ) -> DispatchResult {
+        pallet_uniques::Pallet::<T, I>::set_attribute(
+            RawOrigin::Root.into(),
+            collection.clone(),
+            Some(item.clone()),
+            key.to_vec()
+                .try_into()
+                .map_err(|_| TokenError::Unsupported)?,
+            value
+                .to_vec()
+                .try_into()
+                .map_err(|_| TokenError::Unsupported)?,
+        )
+    }
+}
@@ -10,11 +10,17 @@ use codec::alloc::string::ToString;
 use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
 use frame_system::RawOrigin;
 use primitives::currency::CurrencyId;
+use sp_core::{crypto::KeyTypeId, sr25519};
 use sp_runtime::traits::{BlakeTwo256, Hash, UniqueSaturatedFrom};
 
 const SEED: u32 = 0;
 
 benchmarks! {
+    where_clause { where
+        T::OffchainSignature: From<sr25519::Signature>,
+        T::OffchainPublic: From<sr25519::Public>,
+    }
+
     start_round {
         let alice: T::AccountId = account("alice", 0, SEED);
     }: _(RawOrigin::Root, 1u32, CurrencyId::DORA, "dora".to_string().into(), alice, 1u128)
@@ -56,23 +62,535 @@ benchmarks! {
     }: _(RawOrigin::Signed(voter), CurrencyId::DORA, 1u32, project_hash, 12)
 
     end_round {
+        let p in 1 .. T::MaxProjectsPerRound::get();
+        let c in 1 .. T::MaxVotesPerRound::get();
+        // every participant voted at least once
+        let n in 1 .. T::MaxVotesPerRound::get();
+        let s in 1 .. T::MaxSponsors::get();
+        let alice: T::AccountId = account("alice", 0, SEED);
+
+        // the matching formula is weighed apart, see `pairwise_matching`
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+
+        let token_amount = BalanceOf::<T>::unique_saturated_from(100_000_000_000_000u128);
+
+        let _ = QuadraticFunding::<T>::donate(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice)), 1u32, token_amount, CurrencyId::DORA);
+        // sponsors whose conditions do not hold, their sponsorship is voided
+        let conditions = SponsorConditions { min_projects: u32::MAX, expiry: None };
+        for i in 1 .. s {
+            let sponsor: T::AccountId = account("sponsor", i, SEED);
+            let _ = T::MultiCurrency::deposit(CurrencyId::DORA, &sponsor, token_amount);
+            let _ = QuadraticFunding::<T>::sponsor(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(sponsor)), 1u32, token_amount, CurrencyId::DORA, conditions.clone());
+        }
+
+        for i in 0 .. p {
+            let owner: T::AccountId = account("owner", i, SEED);
+            let _ = QuadraticFunding::<T>::register_project(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(owner)), 1u32, T::Hashing::hash_of(&i), "hack".to_string().into());
+        }
+        // the votes are spread over the projects, every voter votes for as many as it may
+        let per_voter = p.min(T::MaxVotedProjects::get());
+        let voter_amount = BalanceOf::<T>::unique_saturated_from(100_000_000_000_000u128.saturating_mul(per_voter as u128));
+        for k in 0 .. c {
+            let voter: T::AccountId = account("voter", k / per_voter, SEED);
+            if k % per_voter == 0 {
+                let _ = T::MultiCurrency::deposit(CurrencyId::DORA, &voter, voter_amount);
+            }
+            let _ = QuadraticFunding::<T>::vote(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(voter)), CurrencyId::DORA, 1u32, T::Hashing::hash_of(&(k % p)), 12);
+        }
+        // the other participants only weigh on the release of their stake
+        let voters = (c + per_voter - 1) / per_voter;
+        for i in voters .. n.max(voters) {
+            let voter: T::AccountId = account("voter", i, SEED);
+            let stake = T::ReserveUnit::get();
+            let _ = T::MultiCurrency::deposit(CurrencyId::DORA, &voter, token_amount);
+            let _ = T::Currency::reserve_named(&RESERVE_ID, &voter, stake.saturated_into());
+            RoundReserves::<T>::insert(1u32, &voter, stake);
+            RoundParticipants::<T>::insert(1u32, &voter, true);
+            RoundSizes::<T>::mutate(1u32, |size| size.participants.saturating_inc());
+        }
+        let witness = QuadraticFunding::<T>::round_witness(1u32);
+    }: _(RawOrigin::Root, 1u32, witness)
+    verify {
+        assert!(!QuadraticFunding::<T>::rounds(1u32).unwrap().ongoing);
+    }
+
+    pairwise_matching {
+        let max_voters = T::MaxVotersPerProject::get();
+        let q in 0 .. T::MaxVotesPerRound::get().saturating_mul(max_voters.saturating_sub(1)) / 2;
+        // projects of at most `MaxVotersPerProject` voters, until they have `q` pairs of voters
+        let mut projects: Vec<Vec<Contribution<T::AccountId>>> = vec![Vec::new()];
+        let mut pairs = 0u32;
+        let mut i = 0u32;
+        while pairs < q {
+            if projects.last().map_or(true, |voters| voters.len() as u32 >= max_voters) {
+                projects.push(Vec::new());
+            }
+            let voters = projects.last_mut().unwrap();
+            pairs = pairs.saturating_add(voters.len() as u32);
+            voters.push(Contribution { who: account("voter", i, SEED), ballots: 12, amount: 144 });
+            i += 1;
+        }
+    }: {
+        MatchingMode::PairwiseBounded(1_000_000_000_000u128).weights(&projects);
+    }
+
+    set_matching_mode {
+        let alice: T::AccountId = account("alice", 0, SEED);
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+    }: _(RawOrigin::Root, 1u32, MatchingMode::Clr)
+
+    set_max_match_per_project {
+        let alice: T::AccountId = account("alice", 0, SEED);
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+    }: _(RawOrigin::Root, 1u32, Perbill::from_percent(25))
+
+    sponsor {
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let token_amount = BalanceOf::<T>::unique_saturated_from(100_000_000_000_000u128);
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+        let conditions = SponsorConditions { min_projects: 1, expiry: None };
+    }: _(RawOrigin::Signed(alice), 1u32, token_amount, CurrencyId::DORA, conditions)
+
+    refund_sponsor {
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let token_amount = BalanceOf::<T>::unique_saturated_from(100_000_000_000_000u128);
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+        let _ = QuadraticFunding::<T>::donate(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice.clone())), 1u32, token_amount, CurrencyId::DORA);
+        let _ = QuadraticFunding::<T>::end_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, QuadraticFunding::<T>::round_witness(1u32));
+    }: _(RawOrigin::Signed(alice.clone()), 1u32, alice.clone())
+
+    fund_round_from_treasury {
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let token_amount = BalanceOf::<T>::unique_saturated_from(100_000_000_000_000u128);
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+    }: _(RawOrigin::Root, 1u32, token_amount)
+
+    set_fee_destination {
+    }: _(RawOrigin::Root, FeeDestination::Burn)
+
+    sweep_fees {
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let token_amount = BalanceOf::<T>::unique_saturated_from(100_000_000_000_000u128);
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 2u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+        let _ = QuadraticFunding::<T>::donate(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice.clone())), 1u32, token_amount, CurrencyId::DORA);
+        // sweeping into a round is the heaviest destination
+        let _ = QuadraticFunding::<T>::set_fee_destination(<T as frame_system::Config>::Origin::from(RawOrigin::Root), FeeDestination::Round(2));
+    }: _(RawOrigin::Signed(alice), CurrencyId::DORA)
+
+    set_milestones {
+        let m in 1 .. T::MaxMilestones::get();
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let bob: T::AccountId = account("bob", 0, SEED);
+        let project_hash = T::Hashing::hash_of(&1);
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+        let _ = QuadraticFunding::<T>::register_project(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(bob.clone())), 1u32, project_hash, "hack".to_string().into());
+        let mut shares = vec![Perbill::from_rational(1u32, m); m as usize - 1];
+        shares.push(Perbill::from_parts(Perbill::one().deconstruct() - Perbill::from_rational(1u32, m).deconstruct() * (m - 1)));
+    }: _(RawOrigin::Signed(bob), 1u32, project_hash, shares)
+
+    approve_milestone {
         let alice: T::AccountId = account("alice", 0, SEED);
         let bob: T::AccountId = account("bob", 0, SEED);
         let voter: T::AccountId = account("charlie", 0, SEED);
+        let project_hash = T::Hashing::hash_of(&1);
+        let token_amount = BalanceOf::<T>::unique_saturated_from(100_000_000_000_000u128);
 
         let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+        let _ = QuadraticFunding::<T>::donate(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice.clone())), 1u32, token_amount, CurrencyId::DORA);
+        let _ = QuadraticFunding::<T>::register_project(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(bob.clone())), 1u32, project_hash, "hack".to_string().into());
+        let _ = QuadraticFunding::<T>::set_milestones(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(bob)), 1u32, project_hash, vec![Perbill::from_percent(50), Perbill::from_percent(50)]);
+        let _ = QuadraticFunding::<T>::vote(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(voter)), CurrencyId::DORA, 1u32, project_hash, 12);
+        let _ = QuadraticFunding::<T>::end_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, QuadraticFunding::<T>::round_witness(1u32));
+    }: _(RawOrigin::Signed(alice), 1u32, project_hash, 0)
 
+    reclaim_escrow {
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let bob: T::AccountId = account("bob", 0, SEED);
+        let voter: T::AccountId = account("charlie", 0, SEED);
+        let project_hash = T::Hashing::hash_of(&1);
         let token_amount = BalanceOf::<T>::unique_saturated_from(100_000_000_000_000u128);
 
-        let _ = QuadraticFunding::<T>::donate(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice)), 1u32, token_amount, CurrencyId::DORA);
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+        let _ = QuadraticFunding::<T>::donate(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice.clone())), 1u32, token_amount, CurrencyId::DORA);
+        let _ = QuadraticFunding::<T>::register_project(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(bob.clone())), 1u32, project_hash, "hack".to_string().into());
+        let _ = QuadraticFunding::<T>::set_milestones(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(bob)), 1u32, project_hash, vec![Perbill::one()]);
+        let _ = QuadraticFunding::<T>::vote(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(voter)), CurrencyId::DORA, 1u32, project_hash, 12);
+        let _ = QuadraticFunding::<T>::end_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, QuadraticFunding::<T>::round_witness(1u32));
+        frame_system::Pallet::<T>::set_block_number(frame_system::Pallet::<T>::block_number() + T::MilestoneDeadline::get() + 1u32.into());
+    }: _(RawOrigin::Signed(alice), 1u32, project_hash)
+
+    set_vesting_schedule {
+        let alice: T::AccountId = account("alice", 0, SEED);
 
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+        let schedule = VestingSchedule { start: 10u32.into(), duration: 100u32.into(), cliff: 10u32.into() };
+    }: _(RawOrigin::Root, 1u32, schedule)
+
+    claim_vested {
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let bob: T::AccountId = account("bob", 0, SEED);
+        let voter: T::AccountId = account("charlie", 0, SEED);
         let project_hash = T::Hashing::hash_of(&1);
-        //
-        // let project_hash = BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 0u128));
+        let token_amount = BalanceOf::<T>::unique_saturated_from(100_000_000_000_000u128);
 
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+        let schedule = VestingSchedule { start: 0u32.into(), duration: 100u32.into(), cliff: 0u32.into() };
+        let _ = QuadraticFunding::<T>::set_vesting_schedule(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, schedule);
+        let _ = QuadraticFunding::<T>::donate(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice.clone())), 1u32, token_amount, CurrencyId::DORA);
+        let _ = QuadraticFunding::<T>::register_project(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(bob.clone())), 1u32, project_hash, "hack".to_string().into());
+        let _ = QuadraticFunding::<T>::vote(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(voter)), CurrencyId::DORA, 1u32, project_hash, 12);
+        let _ = QuadraticFunding::<T>::end_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, QuadraticFunding::<T>::round_witness(1u32));
+        frame_system::Pallet::<T>::set_block_number(50u32.into());
+    }: _(RawOrigin::Signed(bob), 1u32, project_hash)
+
+    revoke_vesting {
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let bob: T::AccountId = account("bob", 0, SEED);
+        let voter: T::AccountId = account("charlie", 0, SEED);
+        let project_hash = T::Hashing::hash_of(&1);
+        let token_amount = BalanceOf::<T>::unique_saturated_from(100_000_000_000_000u128);
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+        let schedule = VestingSchedule { start: 0u32.into(), duration: 100u32.into(), cliff: 0u32.into() };
+        let _ = QuadraticFunding::<T>::set_vesting_schedule(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, schedule);
+        let _ = QuadraticFunding::<T>::donate(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice.clone())), 1u32, token_amount, CurrencyId::DORA);
         let _ = QuadraticFunding::<T>::register_project(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(bob)), 1u32, project_hash, "hack".to_string().into());
         let _ = QuadraticFunding::<T>::vote(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(voter)), CurrencyId::DORA, 1u32, project_hash, 12);
+        let _ = QuadraticFunding::<T>::end_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, QuadraticFunding::<T>::round_witness(1u32));
+        frame_system::Pallet::<T>::set_block_number(50u32.into());
+    }: _(RawOrigin::Signed(alice), 1u32, project_hash)
+
+    set_challenge_period {
+        let alice: T::AccountId = account("alice", 0, SEED);
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+    }: _(RawOrigin::Root, 1u32, 100u32.into())
+
+    flag_project {
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let bob: T::AccountId = account("bob", 0, SEED);
+        let project_hash = T::Hashing::hash_of(&1);
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+        let _ = QuadraticFunding::<T>::set_challenge_period(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, 100u32.into());
+        let _ = QuadraticFunding::<T>::register_project(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(bob)), 1u32, project_hash, "hack".to_string().into());
+        let _ = QuadraticFunding::<T>::end_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, QuadraticFunding::<T>::round_witness(1u32));
+    }: _(RawOrigin::Signed(alice), 1u32, project_hash, T::Hashing::hash_of(&2))
+
+    resolve_flag {
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let bob: T::AccountId = account("bob", 0, SEED);
+        let voter: T::AccountId = account("charlie", 0, SEED);
+        let project_hash = T::Hashing::hash_of(&1);
+        let other_hash = T::Hashing::hash_of(&2);
+        let token_amount = BalanceOf::<T>::unique_saturated_from(100_000_000_000_000u128);
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+        let _ = QuadraticFunding::<T>::set_challenge_period(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, 100u32.into());
+        let _ = QuadraticFunding::<T>::donate(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice.clone())), 1u32, token_amount, CurrencyId::DORA);
+        let _ = QuadraticFunding::<T>::register_project(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(bob.clone())), 1u32, project_hash, "hack".to_string().into());
+        let _ = QuadraticFunding::<T>::register_project(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(bob)), 1u32, other_hash, "other".to_string().into());
+        let _ = QuadraticFunding::<T>::vote(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(voter.clone())), CurrencyId::DORA, 1u32, project_hash, 12);
+        let _ = QuadraticFunding::<T>::vote(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(voter)), CurrencyId::DORA, 1u32, other_hash, 12);
+        let _ = QuadraticFunding::<T>::end_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, QuadraticFunding::<T>::round_witness(1u32));
+        let _ = QuadraticFunding::<T>::flag_project(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice)), 1u32, project_hash, T::Hashing::hash_of(&3));
+    }: _(RawOrigin::Root, 1u32, project_hash, FlagResolution::Redistribute)
+
+    finalize_round {
+        let p in 1 .. 100;
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let voter: T::AccountId = account("charlie", 0, SEED);
+        let token_amount = BalanceOf::<T>::unique_saturated_from(100_000_000_000_000u128);
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+        let _ = QuadraticFunding::<T>::set_challenge_period(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, 100u32.into());
+        let _ = QuadraticFunding::<T>::donate(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice.clone())), 1u32, token_amount, CurrencyId::DORA);
+        for i in 0 .. p {
+            let owner: T::AccountId = account("owner", i, SEED);
+            let _ = QuadraticFunding::<T>::register_project(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(owner)), 1u32, T::Hashing::hash_of(&i), "hack".to_string().into());
+        }
+        let _ = QuadraticFunding::<T>::vote(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(voter)), CurrencyId::DORA, 1u32, T::Hashing::hash_of(&0u32), 12);
+        let _ = QuadraticFunding::<T>::end_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, QuadraticFunding::<T>::round_witness(1u32));
+        frame_system::Pallet::<T>::set_block_number(frame_system::Pallet::<T>::block_number() + 101u32.into());
+    }: _(RawOrigin::Signed(alice), 1u32, p)
+
+    set_conviction_period {
+        let alice: T::AccountId = account("alice", 0, SEED);
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+    }: _(RawOrigin::Root, 1u32, 100u32.into())
+
+    vote_with_conviction {
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let bob: T::AccountId = account("bob", 0, SEED);
+        let voter: T::AccountId = account("charlie", 0, SEED);
+        let project_hash = T::Hashing::hash_of(&1);
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+        let _ = QuadraticFunding::<T>::set_conviction_period(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, 100u32.into());
+        let _ = QuadraticFunding::<T>::register_project(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(bob)), 1u32, project_hash, "hack".to_string().into());
+    }: _(RawOrigin::Signed(voter), CurrencyId::DORA, 1u32, project_hash, 12, Conviction::Locked6x)
+
+    unlock_conviction {
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let bob: T::AccountId = account("bob", 0, SEED);
+        let voter: T::AccountId = account("charlie", 0, SEED);
+        let project_hash = T::Hashing::hash_of(&1);
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+        let _ = QuadraticFunding::<T>::set_conviction_period(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, 100u32.into());
+        let _ = QuadraticFunding::<T>::register_project(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(bob)), 1u32, project_hash, "hack".to_string().into());
+        let _ = QuadraticFunding::<T>::vote_with_conviction(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(voter.clone())), CurrencyId::DORA, 1u32, project_hash, 12, Conviction::Locked2x);
+        let _ = QuadraticFunding::<T>::end_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, QuadraticFunding::<T>::round_witness(1u32));
+        frame_system::Pallet::<T>::set_block_number(frame_system::Pallet::<T>::block_number() + 100u32.into());
+    }: _(RawOrigin::Signed(alice), 1u32, voter)
+
+    delegate_votes {
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let voter: T::AccountId = account("charlie", 0, SEED);
+        let delegate: T::AccountId = account("dave", 0, SEED);
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+    }: _(RawOrigin::Signed(voter), 1u32, delegate, 100_000_000_000_000u128)
+
+    undelegate_votes {
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let voter: T::AccountId = account("charlie", 0, SEED);
+        let delegate: T::AccountId = account("dave", 0, SEED);
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+        let _ = QuadraticFunding::<T>::delegate_votes(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(voter.clone())), 1u32, delegate, 100_000_000_000_000u128);
+    }: _(RawOrigin::Signed(voter), 1u32)
+
+    vote_as_delegate {
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let bob: T::AccountId = account("bob", 0, SEED);
+        let voter: T::AccountId = account("charlie", 0, SEED);
+        let delegate: T::AccountId = account("dave", 0, SEED);
+        let project_hash = T::Hashing::hash_of(&1);
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+        let _ = QuadraticFunding::<T>::register_project(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(bob)), 1u32, project_hash, "hack".to_string().into());
+        let _ = QuadraticFunding::<T>::delegate_votes(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(voter.clone())), 1u32, delegate.clone(), u128::MAX);
+    }: _(RawOrigin::Signed(delegate), CurrencyId::DORA, 1u32, project_hash, 12, voter)
+
+    set_eligibility_rule {
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let rule = EligibilityRule {
+            currency_id: CurrencyId::KSM,
+            min_balance: 1_000u128,
+            snapshot: Some(100u32.into()),
+        };
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+    }: _(RawOrigin::Root, 1u32, Some(rule))
+
+    set_voter_limits {
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let limits = VoterLimits {
+            max_spend: Some(100_000_000_000_000u128),
+            max_ballots_per_project: Some(100u128),
+        };
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+    }: _(RawOrigin::Root, 1u32, limits)
+
+    set_cost_curve {
+        let alice: T::AccountId = account("alice", 0, SEED);
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+        // the support area only goes with the quadratic curve
+        let _ = QuadraticFunding::<T>::set_matching_mode(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, MatchingMode::Clr);
+    }: _(RawOrigin::Root, 1u32, CostCurve::CappedQuadratic(10u128))
+
+    set_round_currency {
+        let alice: T::AccountId = account("alice", 0, SEED);
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+    }: _(RawOrigin::Root, 1u32, CurrencyId::KSM, Some(CurrencyRate::Fixed(FixedU128::saturating_from_integer(20u128))))
+
+    prove_eligibility {
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let voter: T::AccountId = account("charlie", 0, SEED);
+        let token_amount = BalanceOf::<T>::unique_saturated_from(100_000_000_000_000u128);
+        let rule = EligibilityRule {
+            currency_id: CurrencyId::DORA,
+            min_balance: 100_000_000_000_000u128,
+            snapshot: Some(100u32.into()),
+        };
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+        let _ = QuadraticFunding::<T>::set_eligibility_rule(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, Some(rule));
+        let _ = T::MultiCurrency::deposit(CurrencyId::DORA, &voter, token_amount);
+    }: _(RawOrigin::Signed(voter), 1u32)
+
+    create_round_template {
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let template = RoundTemplate {
+            name: "dora".as_bytes().to_vec().try_into().unwrap(),
+            currency_id: CurrencyId::DORA,
+            admin: alice,
+            round_reserve: 1u128,
+            matching: MatchingMode::Clr,
+            max_match_per_project: Perbill::from_percent(50),
+            eligibility: None,
+            limits: Default::default(),
+            cost_curve: Default::default(),
+            challenge_period: 100u32.into(),
+            conviction_period: 100u32.into(),
+        };
+    }: _(RawOrigin::Root, 1u32, template)
+
+    remove_round_template {
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let template = RoundTemplate {
+            name: "dora".as_bytes().to_vec().try_into().unwrap(),
+            currency_id: CurrencyId::DORA,
+            admin: alice,
+            round_reserve: 1u128,
+            matching: MatchingMode::Clr,
+            max_match_per_project: Perbill::from_percent(50),
+            eligibility: None,
+            limits: Default::default(),
+            cost_curve: Default::default(),
+            challenge_period: 100u32.into(),
+            conviction_period: 100u32.into(),
+        };
+
+        let _ = QuadraticFunding::<T>::create_round_template(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, template);
     }: _(RawOrigin::Root, 1u32)
+
+    start_round_from_template {
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let template = RoundTemplate {
+            name: "dora".as_bytes().to_vec().try_into().unwrap(),
+            currency_id: CurrencyId::DORA,
+            admin: alice,
+            round_reserve: 1u128,
+            matching: MatchingMode::Clr,
+            max_match_per_project: Perbill::from_percent(50),
+            eligibility: None,
+            limits: Default::default(),
+            cost_curve: Default::default(),
+            challenge_period: 100u32.into(),
+            conviction_period: 100u32.into(),
+        };
+
+        let _ = QuadraticFunding::<T>::create_round_template(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, template);
+    }: _(RawOrigin::Root, 1u32, 1u32)
+
+    clone_round {
+        let alice: T::AccountId = account("alice", 0, SEED);
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+        let _ = QuadraticFunding::<T>::set_challenge_period(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, 100u32.into());
+        let _ = QuadraticFunding::<T>::set_conviction_period(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, 100u32.into());
+        let _ = QuadraticFunding::<T>::end_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, QuadraticFunding::<T>::round_witness(1u32));
+    }: _(RawOrigin::Root, 2u32, 1u32)
+
+    archive_round {
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let bob: T::AccountId = account("bob", 0, SEED);
+        let voter: T::AccountId = account("charlie", 0, SEED);
+        let project_hash = T::Hashing::hash_of(&1);
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+        let _ = QuadraticFunding::<T>::register_project(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(bob)), 1u32, project_hash, "hack".to_string().into());
+        let _ = QuadraticFunding::<T>::vote(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(voter)), CurrencyId::DORA, 1u32, project_hash, 12);
+        let _ = QuadraticFunding::<T>::end_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, QuadraticFunding::<T>::round_witness(1u32));
+        frame_system::Pallet::<T>::set_block_number(frame_system::Pallet::<T>::block_number() + T::ArchiveDelay::get());
+    }: _(RawOrigin::Signed(alice), 1u32)
+
+    prune_round {
+        let e in 1 .. 100;
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let bob: T::AccountId = account("bob", 0, SEED);
+        let project_hash = T::Hashing::hash_of(&1);
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+        let _ = QuadraticFunding::<T>::register_project(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(bob)), 1u32, project_hash, "hack".to_string().into());
+        for i in 0 .. e {
+            let voter: T::AccountId = account("voter", i, SEED);
+            let _ = QuadraticFunding::<T>::vote(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(voter)), CurrencyId::DORA, 1u32, project_hash, 1);
+        }
+        let _ = QuadraticFunding::<T>::end_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, QuadraticFunding::<T>::round_witness(1u32));
+        frame_system::Pallet::<T>::set_block_number(frame_system::Pallet::<T>::block_number() + T::ArchiveDelay::get());
+        let _ = QuadraticFunding::<T>::archive_round(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice)), 1u32);
+    }: {
+        QuadraticFunding::<T>::prune_round(1u32, e);
+    }
+
+    vote_signed {
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let bob: T::AccountId = account("bob", 0, SEED);
+        let relayer: T::AccountId = account("dave", 0, SEED);
+        let project_hash = T::Hashing::hash_of(&1);
+        let key_type = KeyTypeId(*b"qfvt");
+        let public = sp_io::crypto::sr25519_generate(key_type, None);
+        let signer = T::OffchainPublic::from(public).into_account();
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+        let _ = QuadraticFunding::<T>::register_project(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(bob)), 1u32, project_hash, "hack".to_string().into());
+        let payload = VotePayload {
+            currency_id: CurrencyId::DORA,
+            round_id: 1u32,
+            project: project_hash,
+            ballot: 12,
+            nonce: 0,
+            expiry: 100u32.into(),
+        };
+        let signature: T::OffchainSignature = sp_io::crypto::sr25519_sign(key_type, &public, &QuadraticFunding::<T>::vote_signing_payload(&payload)).unwrap().into();
+    }: _(RawOrigin::Signed(relayer), payload, signature, signer)
+
+    claim_voter_badge {
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let bob: T::AccountId = account("bob", 0, SEED);
+        let voter: T::AccountId = account("charlie", 0, SEED);
+        let project_hash = T::Hashing::hash_of(&1);
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+        let _ = QuadraticFunding::<T>::register_project(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(bob)), 1u32, project_hash, "hack".to_string().into());
+        let _ = QuadraticFunding::<T>::vote(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(voter.clone())), CurrencyId::DORA, 1u32, project_hash, 12);
+        let _ = QuadraticFunding::<T>::end_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, QuadraticFunding::<T>::round_witness(1u32));
+    }: _(RawOrigin::Signed(voter), 1u32)
+
+    claim_project_badge {
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let bob: T::AccountId = account("bob", 0, SEED);
+        let voter: T::AccountId = account("charlie", 0, SEED);
+        let project_hash = T::Hashing::hash_of(&1);
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+        let _ = QuadraticFunding::<T>::register_project(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(bob.clone())), 1u32, project_hash, "hack".to_string().into());
+        let _ = QuadraticFunding::<T>::register_project(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(alice)), 1u32, T::Hashing::hash_of(&2), "hack".to_string().into());
+        let _ = QuadraticFunding::<T>::vote(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(voter)), CurrencyId::DORA, 1u32, project_hash, 12);
+        let _ = QuadraticFunding::<T>::end_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, QuadraticFunding::<T>::round_witness(1u32));
+    }: _(RawOrigin::Signed(bob), 1u32, project_hash)
+
+    unlock_eligibility {
+        let alice: T::AccountId = account("alice", 0, SEED);
+        let voter: T::AccountId = account("charlie", 0, SEED);
+        let token_amount = BalanceOf::<T>::unique_saturated_from(100_000_000_000_000u128);
+        let rule = EligibilityRule {
+            currency_id: CurrencyId::DORA,
+            min_balance: 100_000_000_000_000u128,
+            snapshot: Some(100u32.into()),
+        };
+
+        let _ = QuadraticFunding::<T>::start_round(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, CurrencyId::DORA, "dora".to_string().into(), alice.clone(), 1u128);
+        let _ = QuadraticFunding::<T>::set_eligibility_rule(<T as frame_system::Config>::Origin::from(RawOrigin::Root), 1u32, Some(rule));
+        let _ = T::MultiCurrency::deposit(CurrencyId::DORA, &voter, token_amount);
+        let _ = QuadraticFunding::<T>::prove_eligibility(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(voter.clone())), 1u32);
+        frame_system::Pallet::<T>::set_block_number(101u32.into());
+    }: _(RawOrigin::Signed(alice), 1u32, voter)
 }
 
 impl_benchmark_test_suite!(
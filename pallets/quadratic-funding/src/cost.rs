@@ -0,0 +1,116 @@
+//! Cost curves pricing the ballots of a voter for a project.
+//!
+//! The n-th ballot of a voter for a project costs `marginal(n)` units of vote, a curve returns
+//! the cost of `ballot` more ballots for a voter who already gave `voted` ballots, which is the
+//! sum of their marginal costs. New curves only need to implement [`VoteCostCurve`] and be added
+//! to [`CostCurve`].
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// Price the ballots of a voter for a project, in units of vote.
+pub trait VoteCostCurve {
+    /// Cost of the ballots `voted + 1 ..= voted + ballot`.
+    fn cost(&self, voted: u128, ballot: u128) -> u128;
+}
+
+/// The cost curve used by a round.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub enum CostCurve {
+    /// The n-th ballot costs n, the original dora pricing.
+    Quadratic,
+    /// Every ballot costs 1.
+    Linear,
+    /// The n-th ballot costs n, up to the given maximum marginal cost.
+    CappedQuadratic(u128),
+    /// The n-th ballot costs `2^(n-1)`.
+    Exponential,
+}
+
+impl Default for CostCurve {
+    fn default() -> Self {
+        CostCurve::Quadratic
+    }
+}
+
+impl VoteCostCurve for CostCurve {
+    fn cost(&self, voted: u128, ballot: u128) -> u128 {
+        match self {
+            CostCurve::Quadratic => Quadratic.cost(voted, ballot),
+            CostCurve::Linear => Linear.cost(voted, ballot),
+            CostCurve::CappedQuadratic(cap) => CappedQuadratic { cap: *cap }.cost(voted, ballot),
+            CostCurve::Exponential => Exponential.cost(voted, ballot),
+        }
+    }
+}
+
+/// `ballot * (ballot + 1) / 2 + ballot * voted`.
+pub struct Quadratic;
+
+impl VoteCostCurve for Quadratic {
+    fn cost(&self, voted: u128, ballot: u128) -> u128 {
+        triangular(ballot).saturating_add(ballot.saturating_mul(voted))
+    }
+}
+
+/// `ballot`.
+pub struct Linear;
+
+impl VoteCostCurve for Linear {
+    fn cost(&self, _voted: u128, ballot: u128) -> u128 {
+        ballot
+    }
+}
+
+/// Quadratic until the marginal cost reaches `cap`, linear afterwards.
+pub struct CappedQuadratic {
+    pub cap: u128,
+}
+
+impl CappedQuadratic {
+    /// Cost of the first `n` ballots.
+    fn total(&self, n: u128) -> u128 {
+        if n <= self.cap {
+            triangular(n)
+        } else {
+            triangular(self.cap).saturating_add((n - self.cap).saturating_mul(self.cap))
+        }
+    }
+}
+
+impl VoteCostCurve for CappedQuadratic {
+    fn cost(&self, voted: u128, ballot: u128) -> u128 {
+        self.total(voted.saturating_add(ballot))
+            .saturating_sub(self.total(voted))
+    }
+}
+
+/// `2^(voted + ballot) - 2^voted`.
+pub struct Exponential;
+
+impl Exponential {
+    /// Cost of the first `n` ballots, `2^n - 1`.
+    fn total(n: u128) -> u128 {
+        if n >= 128 {
+            u128::MAX
+        } else {
+            (1u128 << n) - 1
+        }
+    }
+}
+
+impl VoteCostCurve for Exponential {
+    fn cost(&self, voted: u128, ballot: u128) -> u128 {
+        Self::total(voted.saturating_add(ballot)).saturating_sub(Self::total(voted))
+    }
+}
+
+/// `n * (n + 1) / 2`.
+fn triangular(n: u128) -> u128 {
+    if n % 2 == 0 {
+        (n / 2).saturating_mul(n.saturating_add(1))
+    } else {
+        n.saturating_mul(n / 2 + 1)
+    }
+}
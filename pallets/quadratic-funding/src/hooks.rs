@@ -0,0 +1,41 @@
+//! Hooks letting other pallets react to what happens in the rounds.
+//!
+//! They are called from the extrinsics of the pallet, so their weight is paid by the caller of
+//! the extrinsic and implementations should stay light. Several hooks can be combined in a
+//! tuple.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// Called when a round is started.
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+pub trait OnRoundStarted<AccountId> {
+    fn on_round_started(round_id: u32, admin: &AccountId);
+}
+
+/// Called when a vote is counted, whether cast by the voter itself or by a delegate.
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+pub trait OnVote<AccountId, Hash> {
+    /// `amount` is what the ballots cost in the currency of the round, fees included.
+    fn on_vote(round_id: u32, project: &Hash, voter: &AccountId, ballot: u128, amount: u128);
+}
+
+/// Called once the payouts of a round are fixed, with the results of each of its projects: when
+/// the round ends, or when it is finalized if it has a challenge period.
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+pub trait OnRoundEnded<AccountId, Hash> {
+    fn on_round_ended(round_id: u32, results: &[ProjectResult<AccountId, Hash>]);
+}
+
+/// What a project got out of a round.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct ProjectResult<AccountId, Hash> {
+    pub hash: Hash,
+    pub owner: AccountId,
+    pub total_votes: u128,
+    /// Contributions of the voters, fees excluded
+    pub grants: u128,
+    /// Matching from the support pool, paid once the round is settled
+    pub support_fund: u128,
+}
@@ -0,0 +1,48 @@
+//! Driving the rounds from other pallets, or from a chain extension of contracts, without
+//! constructing calls.
+
+use crate::hooks::ProjectResult;
+use sp_runtime::DispatchResult;
+use sp_std::vec::Vec;
+
+/// The rounds of quadratic funding, as seen by other runtime code.
+///
+/// The origin is checked as if the matching extrinsic was dispatched with it.
+pub trait QuadraticFundingInterface<AccountId, Hash, CurrencyId> {
+    type Origin;
+
+    /// Start a round with the default settings, `origin` must be the admin origin.
+    fn create_round(
+        origin: Self::Origin,
+        round_id: u32,
+        currency_id: CurrencyId,
+        name: Vec<u8>,
+        admin: AccountId,
+        round_reserve: u128,
+    ) -> DispatchResult;
+
+    /// Register a project owned by `owner`, `origin` must be the admin origin or signed by
+    /// `owner`.
+    fn register_project_for(
+        origin: Self::Origin,
+        owner: AccountId,
+        round_id: u32,
+        hash: Hash,
+        name: Vec<u8>,
+    ) -> DispatchResult;
+
+    /// Vote for a project with the funds of `voter`, `origin` must be signed by `voter` or by a
+    /// delegate of `voter` in the round, within its budget.
+    fn vote_for(
+        origin: Self::Origin,
+        voter: AccountId,
+        currency_id: CurrencyId,
+        round_id: u32,
+        hash: Hash,
+        ballot: u128,
+    ) -> DispatchResult;
+
+    /// The results of the projects of an ended round, archived or not. None when the round does
+    /// not exist or is still ongoing.
+    fn round_results(round_id: u32) -> Option<Vec<ProjectResult<AccountId, Hash>>>;
+}
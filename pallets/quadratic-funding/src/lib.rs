@@ -3,26 +3,53 @@
 use codec::MaxEncodedLen;
 use frame_support::{
     codec::{Decode, Encode},
-    traits::{Currency, EnsureOrigin, Get, ReservableCurrency},
+    dispatch::DispatchResult,
+    ensure,
+    traits::{
+        tokens::nonfungibles, BalanceStatus, Currency, EnsureOrigin, Get, LockIdentifier, Locker,
+        NamedReservableCurrency, ReservableCurrency, StorageVersion,
+    },
+    weights::Weight,
     BoundedVec, PalletId,
 };
+use frame_system::ensure_signed;
 use orml_traits::{
-    currency::TransferAll, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency,
-    MultiReservableCurrency, NamedMultiReservableCurrency,
+    currency::TransferAll, DataProvider, MultiCurrency, MultiCurrencyExtended,
+    MultiLockableCurrency, MultiReservableCurrency, NamedMultiReservableCurrency,
 };
 pub use pallet::*;
+use primitives::ReserveIdentifier;
 /// Edit this file to define custom logic or remove it if it is not needed.
 /// Learn more about FRAME and the core library of Substrate FRAME pallets:
 /// <https://substrate.dev/docs/en/knowledgebase/runtime/frame>
 use scale_info::TypeInfo;
-use sp_runtime::traits::{AccountIdConversion, Hash};
-use sp_runtime::RuntimeDebug;
+use sp_io::MultiRemovalResults;
+use sp_runtime::traits::{
+    AccountIdConversion, BadOrigin, IdentifyAccount, One, Saturating, Verify, Zero,
+};
+use sp_runtime::{
+    helpers_128bit::multiply_by_rational, DispatchError, FixedPointNumber, FixedU128, Perbill,
+    RuntimeDebug, SaturatedConversion,
+};
 use sp_std::{convert::TryInto, vec, vec::Vec};
 
 mod benchmarking;
 
+pub mod badges;
+pub mod cost;
+pub mod hooks;
+pub mod interface;
+pub mod matching;
+pub mod migrations;
+pub mod price;
 pub mod weights;
 
+pub use cost::{CostCurve, VoteCostCurve};
+pub use hooks::{OnRoundEnded, OnRoundStarted, OnVote, ProjectResult};
+pub use interface::QuadraticFundingInterface;
+pub use matching::{Contribution, MatchingFormula, MatchingMode};
+pub use price::{CurrencyRate, NoPriceFeed};
+
 pub use weights::WeightInfo;
 
 #[cfg(test)]
@@ -31,6 +58,19 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+/// Named reserve of the DORA voters stake to take part in a round.
+pub const RESERVE_ID: ReserveIdentifier = ReserveIdentifier::QuadraticFunding;
+
+/// Prefix of the messages signed for [`Pallet::vote_signed`], so that they can not be taken for
+/// anything else the voter signs.
+pub const VOTE_SIGNING_CONTEXT: &[u8] = b"dora-qf-vote";
+
+/// The most round ids [`Pallet::voter_history`] looks up in a call.
+pub const MAX_HISTORY_ROUNDS: u32 = 100;
+
+/// Version of the layout of the storage, upgraded by the [`migrations`].
+pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 #[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
 pub struct Project<AccountId, BoundedString> {
     pub total_votes: u128,
@@ -42,7 +82,7 @@ pub struct Project<AccountId, BoundedString> {
 }
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
-pub struct Round<AccountId, BoundedString, CurrencyIdOf> {
+pub struct Round<AccountId, BoundedString, CurrencyIdOf, BlockNumber> {
     pub name: BoundedString,
     pub currency_id: CurrencyIdOf,
     pub ongoing: bool,
@@ -52,6 +92,324 @@ pub struct Round<AccountId, BoundedString, CurrencyIdOf> {
     pub total_tax: u128,
     pub round_reserve: u128,
     pub admin: AccountId,
+    pub matching: MatchingMode,
+    /// The maximum share of the support pool a single project can receive
+    pub max_match_per_project: Perbill,
+    /// Number of sponsors who donated to the support pool
+    pub sponsors: u32,
+    /// The part of the support pool which was not distributed to projects, sponsors get it back
+    pub undistributed_pool: u128,
+    /// Holdings required to vote, anyone can vote when none
+    pub eligibility: Option<EligibilityRule<CurrencyIdOf, BlockNumber>>,
+    pub limits: VoterLimits,
+    /// How much the ballots of a voter for a project cost
+    pub cost_curve: CostCurve,
+}
+
+/// How much a single voter can weigh in a round, no limit when none.
+#[derive(Encode, Decode, Clone, Default, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct VoterLimits {
+    /// Maximum amount a voter can spend on ballots in the round, fees included
+    pub max_spend: Option<u128>,
+    /// Maximum number of ballots of a voter for a single project
+    pub max_ballots_per_project: Option<u128>,
+}
+
+/// Holdings a voter needs to vote in a token-gated round.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct EligibilityRule<CurrencyId, BlockNumber> {
+    pub currency_id: CurrencyId,
+    /// Minimum total balance of `currency_id`
+    pub min_balance: u128,
+    /// When set, the holdings are checked by `prove_eligibility` up to this block instead of
+    /// at vote time
+    pub snapshot: Option<BlockNumber>,
+}
+
+/// The projects a voter voted for in a round and how much it paid, fees included.
+#[derive(Encode, Decode, Clone, Default, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct VoterParticipation<BoundedHashes> {
+    pub projects: BoundedHashes,
+    pub spent: u128,
+}
+
+/// How many projects and voters a round has, to weigh the extrinsics going over all of them.
+#[derive(
+    Encode, Decode, Clone, Copy, Default, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug,
+)]
+pub struct RoundSize {
+    pub projects: u32,
+    pub participants: u32,
+    /// The most voters a single project of the round has
+    pub voters_per_project: u32,
+    /// The votes of the round, one per voter and project it voted for
+    pub votes: u32,
+}
+
+/// Upper bounds of the size of a round given to `end_round`, so that its weight is known
+/// before dispatch. See [`Pallet::round_witness`].
+#[derive(
+    Encode, Decode, Clone, Copy, Default, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug,
+)]
+pub struct RoundWitness {
+    pub projects: u32,
+    /// The votes of the round, one per voter and project it voted for
+    pub votes: u32,
+    /// The most voters a single project of the round has
+    pub voters_per_project: u32,
+    pub participants: u32,
+    /// The sponsorships of the round, the treasury included
+    pub sponsors: u32,
+}
+
+/// What is kept of a round once it is archived.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct RoundSummary<AccountId, BoundedString, CurrencyIdOf, BlockNumber> {
+    pub name: BoundedString,
+    pub currency_id: CurrencyIdOf,
+    pub admin: AccountId,
+    pub matching: MatchingMode,
+    pub support_pool: u128,
+    pub total_tax: u128,
+    pub undistributed_pool: u128,
+    pub ended_at: BlockNumber,
+}
+
+/// What is kept of a project once its round is archived.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct ProjectSummary<AccountId, BoundedString> {
+    pub name: BoundedString,
+    pub owner: AccountId,
+    pub total_votes: u128,
+    pub grants: u128,
+    pub support_fund: u128,
+}
+
+/// Settings of a round, recurring rounds are started from a stored template or from a previous
+/// round.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct RoundTemplate<AccountId, BoundedString, CurrencyIdOf, BlockNumber> {
+    pub name: BoundedString,
+    pub currency_id: CurrencyIdOf,
+    pub admin: AccountId,
+    pub round_reserve: u128,
+    pub matching: MatchingMode,
+    pub max_match_per_project: Perbill,
+    pub eligibility: Option<EligibilityRule<CurrencyIdOf, BlockNumber>>,
+    pub limits: VoterLimits,
+    pub cost_curve: CostCurve,
+    /// Zero disables the challenge period
+    pub challenge_period: BlockNumber,
+    /// Zero disables conviction voting
+    pub conviction_period: BlockNumber,
+}
+
+/// Conditions under which a sponsor donates to a round, when they do not hold at the end of the
+/// round the donation is refunded entirely.
+#[derive(Encode, Decode, Clone, Default, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct SponsorConditions<BlockNumber> {
+    /// The minimum number of projects registered in the round
+    pub min_projects: u32,
+    /// The round must end before this block
+    pub expiry: Option<BlockNumber>,
+}
+
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct Sponsorship<BlockNumber> {
+    /// Amount added to the support pool, fee excluded
+    pub amount: u128,
+    pub conditions: SponsorConditions<BlockNumber>,
+    /// The conditions were not met, the donation is not part of the support pool anymore
+    pub void: bool,
+    pub refunded: bool,
+}
+
+/// A tranche of the funds of a project, released once the milestone is approved.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct Milestone {
+    /// Share of the escrowed funds released by this milestone
+    pub share: Perbill,
+    pub released: bool,
+}
+
+/// Funds of a project with milestones, held by the round account after the round ended.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct Escrow<BlockNumber> {
+    /// Grants and support fund of the project
+    pub total: u128,
+    pub released: u128,
+    /// Unreleased funds go to the treasury after this block
+    pub deadline: BlockNumber,
+}
+
+/// A linear schedule paying the funds of the projects of a round over time.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct VestingSchedule<BlockNumber> {
+    /// Block from which the funds start vesting
+    pub start: BlockNumber,
+    /// Number of blocks until the funds are fully vested
+    pub duration: BlockNumber,
+    /// Nothing can be claimed before `start + cliff`
+    pub cliff: BlockNumber,
+}
+
+/// Funds of a project paid through the vesting schedule of its round.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct VestedGrant {
+    /// Grants and support fund of the project, what was vested when it was revoked otherwise
+    pub total: u128,
+    pub claimed: u128,
+    pub revoked: bool,
+}
+
+/// A project flagged during the challenge period of its round, its matching is frozen until
+/// the flag is resolved.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct Flag<AccountId, Hash> {
+    /// The bonded challenger, none when flagged by the challenge origin
+    pub challenger: Option<AccountId>,
+    pub bond: u128,
+    /// Hash of the evidence (collusion, plagiarism...)
+    pub evidence: Hash,
+}
+
+/// How a flag is resolved.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub enum FlagResolution {
+    /// The flag is rejected, the project keeps its matching and the bond is slashed
+    Restore,
+    /// The flag is upheld, the matching of the project is shared between the other projects
+    Redistribute,
+}
+
+/// Boost of the ballots of a voter who keeps as much as they paid locked after the end of the
+/// round, like the conviction of `pallet_democracy`.
+#[derive(
+    Encode,
+    Decode,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    TypeInfo,
+    MaxEncodedLen,
+    RuntimeDebug,
+)]
+pub enum Conviction {
+    /// Ballots count once, nothing is locked
+    None,
+    /// Ballots count twice, locked for one conviction period
+    Locked2x,
+    /// Ballots count 3 times, locked for 2 conviction periods
+    Locked3x,
+    /// Ballots count 4 times, locked for 4 conviction periods
+    Locked4x,
+    /// Ballots count 5 times, locked for 8 conviction periods
+    Locked5x,
+    /// Ballots count 6 times, locked for 16 conviction periods
+    Locked6x,
+}
+
+impl Default for Conviction {
+    fn default() -> Self {
+        Conviction::None
+    }
+}
+
+impl Conviction {
+    /// How many times the ballots count towards the matching
+    pub fn multiplier(self) -> u128 {
+        match self {
+            Conviction::None => 1,
+            Conviction::Locked2x => 2,
+            Conviction::Locked3x => 3,
+            Conviction::Locked4x => 4,
+            Conviction::Locked5x => 5,
+            Conviction::Locked6x => 6,
+        }
+    }
+
+    /// Number of conviction periods the funds stay locked after the end of the round
+    pub fn lock_periods(self) -> u32 {
+        match self {
+            Conviction::None => 0,
+            Conviction::Locked2x => 1,
+            Conviction::Locked3x => 2,
+            Conviction::Locked4x => 4,
+            Conviction::Locked5x => 8,
+            Conviction::Locked6x => 16,
+        }
+    }
+}
+
+/// Funds locked by a voter of a round, for the highest conviction the voter used.
+#[derive(Encode, Decode, Clone, Default, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct ConvictionLock {
+    pub amount: u128,
+    pub conviction: Conviction,
+}
+
+/// Ballots budget a voter delegated to another account for one round.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct VoteDelegation<AccountId> {
+    /// The account casting votes paid from the funds of the delegator
+    pub delegate: AccountId,
+    /// Maximum amount the delegate can spend on ballots, fees included
+    pub budget: u128,
+    pub spent: u128,
+}
+
+/// A vote signed by a voter and submitted by a relayer paying the transaction fee, the voter
+/// pays for the ballots. What the voter signs is given by [`Pallet::vote_signing_payload`].
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo, RuntimeDebug)]
+pub struct VotePayload<CurrencyId, Hash, BlockNumber> {
+    pub currency_id: CurrencyId,
+    pub round_id: u32,
+    pub project: Hash,
+    pub ballot: u128,
+    /// Must be the next signed vote nonce of the voter
+    pub nonce: u64,
+    /// The payload can not be submitted after this block
+    pub expiry: BlockNumber,
+}
+
+/// Where the fees collected by the pallet account are swept to.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub enum FeeDestination {
+    /// The treasury account
+    Treasury,
+    /// Fees are burnt
+    Burn,
+    /// The support pool of the given round, the pallet account sponsors the round with them
+    Round(u32),
+}
+
+impl Default for FeeDestination {
+    fn default() -> Self {
+        FeeDestination::Treasury
+    }
+}
+
+/// Who a participation badge was minted for.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub enum BadgeKind<Hash> {
+    /// A voter of the round
+    Voter,
+    /// The owner of a project funded in the round
+    Project(Hash),
+}
+
+/// The attributes of a participation badge.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct Badge<Hash> {
+    pub round_id: u32,
+    pub kind: BadgeKind<Hash>,
+    /// Rank of a project by the funds it got in the round, starting at 1
+    pub rank: Option<u32>,
+    /// What a voter spent, or what a project got, in the currency of the round
+    pub amount: u128,
 }
 
 type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
@@ -61,6 +419,38 @@ type BalanceOf<T> = <<T as Config>::MultiCurrency as MultiCurrency<AccountIdOf<T
 pub(crate) type CurrencyIdOf<T> = <<T as Config>::MultiCurrency as MultiCurrency<
     <T as frame_system::Config>::AccountId,
 >>::CurrencyId;
+pub(crate) type RoundOf<T> = Round<
+    <T as frame_system::Config>::AccountId,
+    BoundedVec<u8, <T as Config>::NameMaxLength>,
+    CurrencyIdOf<T>,
+    <T as frame_system::Config>::BlockNumber,
+>;
+pub(crate) type VoterParticipationOf<T> = VoterParticipation<
+    BoundedVec<<T as frame_system::Config>::Hash, <T as Config>::MaxVotedProjects>,
+>;
+pub(crate) type RoundSummaryOf<T> = RoundSummary<
+    <T as frame_system::Config>::AccountId,
+    BoundedVec<u8, <T as Config>::NameMaxLength>,
+    CurrencyIdOf<T>,
+    <T as frame_system::Config>::BlockNumber,
+>;
+pub(crate) type ProjectSummaryOf<T> = ProjectSummary<
+    <T as frame_system::Config>::AccountId,
+    BoundedVec<u8, <T as Config>::NameMaxLength>,
+>;
+pub(crate) type RoundTemplateOf<T> = RoundTemplate<
+    <T as frame_system::Config>::AccountId,
+    BoundedVec<u8, <T as Config>::NameMaxLength>,
+    CurrencyIdOf<T>,
+    <T as frame_system::Config>::BlockNumber,
+>;
+pub(crate) type EligibilityRuleOf<T> =
+    EligibilityRule<CurrencyIdOf<T>, <T as frame_system::Config>::BlockNumber>;
+pub(crate) type VotePayloadOf<T> = VotePayload<
+    CurrencyIdOf<T>,
+    <T as frame_system::Config>::Hash,
+    <T as frame_system::Config>::BlockNumber,
+>;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -74,7 +464,8 @@ pub mod pallet {
         /// Because this pallet emits events, it depends on the runtime's definition of an event.
         type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
-        type Currency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
+        type Currency: Currency<Self::AccountId>
+            + NamedReservableCurrency<Self::AccountId, ReserveIdentifier = ReserveIdentifier>;
 
         /// Currency to transfer assets
         type MultiCurrency: TransferAll<Self::AccountId>
@@ -89,6 +480,13 @@ pub mod pallet {
         /// Origin from which admin must come.
         type AdminOrigin: EnsureOrigin<Self::Origin>;
 
+        /// Origin which can move treasury funds into the support pool of a round.
+        type TreasuryOrigin: EnsureOrigin<Self::Origin>;
+
+        /// The account holding the treasury funds.
+        #[pallet::constant]
+        type TreasuryAccount: Get<Self::AccountId>;
+
         // What to do with slashed funds.
         // type Slashed: OnUnbalanced<NegativeImbalanceOf<Self>>;
         // type Slashed: AccountId;
@@ -110,12 +508,94 @@ pub mod pallet {
 
         type ReserveUnit: Get<u128>;
 
+        /// The maximum number of sponsors of a round, the treasury is not counted against it
+        #[pallet::constant]
+        type MaxSponsors: Get<u32>;
+
+        /// The minimum first donation of a sponsor, in the currency of the round
+        #[pallet::constant]
+        type MinSponsorship: Get<u128>;
+
+        /// Origin which can approve the milestones of a project, besides the round admin.
+        type MilestoneReviewerOrigin: EnsureOrigin<Self::Origin>;
+
+        /// The maximum number of milestones of a project
+        #[pallet::constant]
+        type MaxMilestones: Get<u32>;
+
+        /// How long the funds of a project stay in escrow after the end of the round
+        #[pallet::constant]
+        type MilestoneDeadline: Get<Self::BlockNumber>;
+
+        /// Origin which can flag projects without a bond.
+        type ChallengeOrigin: EnsureOrigin<Self::Origin>;
+
+        /// Origin which resolves the flags.
+        type ResolutionOrigin: EnsureOrigin<Self::Origin>;
+
+        /// Amount of DORA reserved by a challenger flagging a project
+        #[pallet::constant]
+        type ChallengeBond: Get<u128>;
+
+        /// How long after its end a round can be archived
+        #[pallet::constant]
+        type ArchiveDelay: Get<Self::BlockNumber>;
+
+        /// The maximum number of projects a voter can vote for in a round
+        #[pallet::constant]
+        type MaxVotedProjects: Get<u32>;
+
+        /// The maximum number of voters of a project in the rounds using the pairwise matching,
+        /// bounding the pairs it goes over
+        #[pallet::constant]
+        type MaxVotersPerProject: Get<u32>;
+
+        /// The maximum number of projects of a round, bounding what ending it goes over
+        #[pallet::constant]
+        type MaxProjectsPerRound: Get<u32>;
+
+        /// The maximum number of votes of a round, one per voter and project it voted for,
+        /// bounding the votes and voters ending it goes over
+        #[pallet::constant]
+        type MaxVotesPerRound: Get<u32>;
+
+        /// Prices of the currencies, used by the rounds accepting a currency at the oracle rate.
+        type PriceFeed: DataProvider<CurrencyIdOf<Self>, FixedU128>;
+
+        /// The maximum number of currencies a round accepts besides its own
+        #[pallet::constant]
+        type MaxRoundCurrencies: Get<u32>;
+
+        /// Signature of the votes submitted by relayers.
+        type OffchainSignature: Verify<Signer = Self::OffchainPublic> + Parameter;
+
+        /// Key signing the votes submitted by relayers.
+        type OffchainPublic: IdentifyAccount<AccountId = Self::AccountId>;
+
+        /// Called when a round is started.
+        type OnRoundStarted: OnRoundStarted<Self::AccountId>;
+
+        /// Called when a vote is counted.
+        type OnVote: OnVote<Self::AccountId, Self::Hash>;
+
+        /// Called when a round is ended, with the results of its projects.
+        type OnRoundEnded: OnRoundEnded<Self::AccountId, Self::Hash>;
+
+        /// Non-fungible tokens minted as participation badges, e.g. `pallet_uniques`.
+        type Badges: nonfungibles::Create<Self::AccountId, CollectionId = u32, ItemId = u32>
+            + nonfungibles::Mutate<Self::AccountId>;
+
+        /// The collection of the participation badges, created by the pallet account
+        #[pallet::constant]
+        type BadgeCollection: Get<u32>;
+
         /// Infomation on runtime weights.
         type WeightInfo: WeightInfo;
     }
 
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     // The pallet's runtime storage items.
@@ -124,37 +604,276 @@ pub mod pallet {
     #[pallet::getter(fn rounds)]
     // Learn more about declaring storage items:
     // https://substrate.dev/docs/en/knowledgebase/runtime/storage#declaring-storage-items
-    pub(super) type Rounds<T: Config> = StorageMap<
+    pub(super) type Rounds<T: Config> = StorageMap<_, Blake2_128Concat, u32, RoundOf<T>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn projects)]
+    pub(super) type Projects<T: Config> = StorageDoubleMap<
         _,
         Blake2_128Concat,
         u32,
-        Round<
-            <T as frame_system::Config>::AccountId,
-            BoundedVec<u8, T::NameMaxLength>,
-            CurrencyIdOf<T>,
-        >,
+        Blake2_128Concat,
+        T::Hash,
+        Project<<T as frame_system::Config>::AccountId, BoundedVec<u8, T::NameMaxLength>>,
     >;
 
+    /// Ballots of the voters of each project, keyed by round first so that the votes of a round
+    /// can be removed at once
     #[pallet::storage]
-    #[pallet::getter(fn projects)]
-    pub(super) type Projects<T: Config> = StorageDoubleMap<
+    pub(super) type ProjectVotes<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Blake2_128Concat, u32>,
+            NMapKey<Blake2_128Concat, T::Hash>,
+            NMapKey<Blake2_128Concat, T::AccountId>,
+        ),
+        u128,
+    >;
+
+    /// Number of voters of each project
+    #[pallet::storage]
+    #[pallet::getter(fn project_voters)]
+    pub(super) type ProjectVoters<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, u32, Blake2_128Concat, T::Hash, u32, ValueQuery>;
+
+    /// Donations of every sponsor of a round
+    #[pallet::storage]
+    #[pallet::getter(fn sponsorships)]
+    pub(super) type Sponsorships<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u32,
+        Blake2_128Concat,
+        T::AccountId,
+        Sponsorship<T::BlockNumber>,
+    >;
+
+    /// Milestones declared by the projects of a round
+    #[pallet::storage]
+    #[pallet::getter(fn milestones)]
+    pub(super) type Milestones<T: Config> = StorageDoubleMap<
         _,
         Blake2_128Concat,
         u32,
         Blake2_128Concat,
         T::Hash,
-        Project<<T as frame_system::Config>::AccountId, BoundedVec<u8, T::NameMaxLength>>,
+        BoundedVec<Milestone, T::MaxMilestones>,
+    >;
+
+    /// Funds of the projects with milestones, released milestone by milestone
+    #[pallet::storage]
+    #[pallet::getter(fn escrows)]
+    pub(super) type Escrows<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u32,
+        Blake2_128Concat,
+        T::Hash,
+        Escrow<T::BlockNumber>,
+    >;
+
+    /// The currencies a round accepts besides its own, with their rate
+    #[pallet::storage]
+    #[pallet::getter(fn round_currency)]
+    pub(super) type RoundCurrencies<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, u32, Blake2_128Concat, CurrencyIdOf<T>, CurrencyRate>;
+
+    /// What the round account holds in each currency, the payouts of the round take the same
+    /// share of every currency
+    #[pallet::storage]
+    #[pallet::getter(fn round_holding)]
+    pub(super) type RoundHoldings<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u32,
+        Blake2_128Concat,
+        CurrencyIdOf<T>,
+        u128,
+        ValueQuery,
+    >;
+
+    /// The value of the holdings of a round, in the currency of the round
+    #[pallet::storage]
+    #[pallet::getter(fn round_held_value)]
+    pub(super) type RoundHeldValue<T: Config> =
+        StorageMap<_, Blake2_128Concat, u32, u128, ValueQuery>;
+
+    /// Grants of the projects paid by the round admin which were voted in another currency than
+    /// the one of the round, held by the round account until the round is settled
+    #[pallet::storage]
+    #[pallet::getter(fn held_grants)]
+    pub(super) type HeldGrants<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, u32, Blake2_128Concat, T::Hash, u128, ValueQuery>;
+
+    /// Vesting schedule paying the projects of a round, if any
+    #[pallet::storage]
+    #[pallet::getter(fn vesting_schedule)]
+    pub(super) type RoundVesting<T: Config> =
+        StorageMap<_, Blake2_128Concat, u32, VestingSchedule<T::BlockNumber>>;
+
+    /// Funds of the projects of the rounds with a vesting schedule
+    #[pallet::storage]
+    #[pallet::getter(fn vested_grants)]
+    pub(super) type VestedGrants<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, u32, Blake2_128Concat, T::Hash, VestedGrant>;
+
+    /// How long projects can be flagged after the end of a round
+    #[pallet::storage]
+    #[pallet::getter(fn challenge_period)]
+    pub(super) type ChallengePeriods<T: Config> =
+        StorageMap<_, Blake2_128Concat, u32, T::BlockNumber, ValueQuery>;
+
+    /// Ended rounds whose matching is not paid yet, until the end of their challenge period
+    #[pallet::storage]
+    #[pallet::getter(fn settlement_deadline)]
+    pub(super) type SettlementDeadlines<T: Config> =
+        StorageMap<_, Blake2_128Concat, u32, T::BlockNumber>;
+
+    /// Flags waiting for a resolution
+    #[pallet::storage]
+    #[pallet::getter(fn flags)]
+    pub(super) type Flags<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u32,
+        Blake2_128Concat,
+        T::Hash,
+        Flag<T::AccountId, T::Hash>,
+    >;
+
+    /// Length of a conviction period of a round, conviction voting is disabled when zero
+    #[pallet::storage]
+    #[pallet::getter(fn conviction_period)]
+    pub(super) type ConvictionPeriods<T: Config> =
+        StorageMap<_, Blake2_128Concat, u32, T::BlockNumber, ValueQuery>;
+
+    /// Funds locked by the conviction voters of a round
+    #[pallet::storage]
+    #[pallet::getter(fn conviction_locks)]
+    pub(super) type ConvictionLocks<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, u32, Blake2_128Concat, T::AccountId, ConvictionLock>;
+
+    /// Extra ballots given by the conviction of the voters, keyed like `ProjectVotes`
+    #[pallet::storage]
+    pub(super) type BoostedVotes<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Blake2_128Concat, u32>,
+            NMapKey<Blake2_128Concat, T::Hash>,
+            NMapKey<Blake2_128Concat, T::AccountId>,
+        ),
+        u128,
+        ValueQuery,
+    >;
+
+    /// Ballots budgets delegated by the voters of a round
+    #[pallet::storage]
+    #[pallet::getter(fn vote_delegations)]
+    pub(super) type VoteDelegations<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u32,
+        Blake2_128Concat,
+        T::AccountId,
+        VoteDelegation<T::AccountId>,
     >;
 
+    /// Projects voted by each voter of a round
+    #[pallet::storage]
+    #[pallet::getter(fn voter_participation)]
+    pub(super) type VoterParticipations<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u32,
+        Blake2_128Concat,
+        T::AccountId,
+        VoterParticipationOf<T>,
+    >;
+
+    /// Summaries of the archived rounds
+    #[pallet::storage]
+    #[pallet::getter(fn archived_rounds)]
+    pub(super) type ArchivedRounds<T: Config> =
+        StorageMap<_, Blake2_128Concat, u32, RoundSummaryOf<T>>;
+
+    /// Summaries of the projects of the archived rounds
+    #[pallet::storage]
+    #[pallet::getter(fn archived_projects)]
+    pub(super) type ArchivedProjects<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, u32, Blake2_128Concat, T::Hash, ProjectSummaryOf<T>>;
+
+    /// Archived rounds whose detailed storage is still being removed in `on_idle`
+    #[pallet::storage]
+    pub(super) type RoundsToPrune<T: Config> = StorageMap<_, Blake2_128Concat, u32, ()>;
+
+    /// Templates new rounds can be started from
+    #[pallet::storage]
+    #[pallet::getter(fn round_templates)]
+    pub(super) type RoundTemplates<T: Config> =
+        StorageMap<_, Blake2_128Concat, u32, RoundTemplateOf<T>>;
+
+    /// Voters who proved their holdings before the snapshot of a token-gated round
+    #[pallet::storage]
+    #[pallet::getter(fn eligible_voters)]
+    pub(super) type EligibleVoters<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, u32, Blake2_128Concat, T::AccountId, bool>;
+
+    /// Currency of the holdings locked by the voters who proved them, until the snapshot
+    #[pallet::storage]
+    #[pallet::getter(fn eligibility_locks)]
+    pub(super) type EligibilityLocks<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, u32, Blake2_128Concat, T::AccountId, CurrencyIdOf<T>>;
+
+    /// Nonce of the next signed vote of each voter
+    #[pallet::storage]
+    #[pallet::getter(fn vote_nonce)]
+    pub(super) type VoteNonces<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u64, ValueQuery>;
+
+    /// Block at which a round ended
+    #[pallet::storage]
+    #[pallet::getter(fn round_ended_at)]
+    pub(super) type RoundEndedAt<T: Config> = StorageMap<_, Blake2_128Concat, u32, T::BlockNumber>;
+
+    /// Where the collected fees go when they are swept
     #[pallet::storage]
-    pub(super) type ProjectVotes<T: Config> =
-        StorageDoubleMap<_, Blake2_128Concat, T::Hash, Blake2_128Concat, T::AccountId, u128>;
+    #[pallet::getter(fn fee_destination)]
+    pub(super) type CurrentFeeDestination<T: Config> = StorageValue<_, FeeDestination, ValueQuery>;
 
+    /// Voters of the rounds, kept once a round is ended until they claim their badge
     #[pallet::storage]
     #[pallet::getter(fn round_participants)]
     pub(super) type RoundParticipants<T: Config> =
         StorageDoubleMap<_, Blake2_128Concat, u32, Blake2_128Concat, T::AccountId, bool>;
 
+    /// DORA reserved by the voters of the ongoing rounds, released when the round is ended
+    #[pallet::storage]
+    #[pallet::getter(fn round_reserves)]
+    pub(super) type RoundReserves<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, u32, Blake2_128Concat, T::AccountId, u128>;
+
+    /// Badges claimed by the owners of funded projects
+    #[pallet::storage]
+    #[pallet::getter(fn project_badges)]
+    pub(super) type ProjectBadges<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, u32, Blake2_128Concat, T::Hash, u32>;
+
+    /// Rank of the projects of a settled round by the funds they got, starting at 1
+    #[pallet::storage]
+    #[pallet::getter(fn project_rank)]
+    pub(super) type ProjectRanks<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, u32, Blake2_128Concat, T::Hash, u32>;
+
+    /// The item of the next badge
+    #[pallet::storage]
+    pub(super) type NextBadgeId<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// Number of projects and voters of the rounds until they are pruned
+    #[pallet::storage]
+    #[pallet::getter(fn round_size)]
+    pub(super) type RoundSizes<T: Config> =
+        StorageMap<_, Blake2_128Concat, u32, RoundSize, ValueQuery>;
+
     // Pallets use events to inform users when important changes are made.
     // https://substrate.dev/docs/en/knowledgebase/runtime/events
     #[pallet::event]
@@ -173,6 +892,72 @@ pub mod pallet {
         RoundEnded(u32),
         /// parameters. [round_id, who, amount]
         DonateSucceed(u32, T::AccountId, BalanceOf<T>),
+        /// parameters. [round_id, matching mode]
+        MatchingModeSet(u32, MatchingMode),
+        /// parameters. [round_id, cost curve]
+        CostCurveSet(u32, CostCurve),
+        /// parameters. [round_id, max share of the support pool per project]
+        MaxMatchPerProjectSet(u32, Perbill),
+        /// parameters. [round_id, sponsor, amount]
+        SponsorRefunded(u32, T::AccountId, u128),
+        /// parameters. [fee destination]
+        FeeDestinationSet(FeeDestination),
+        /// parameters. [currency_id, amount, fee destination]
+        FeesSwept(CurrencyIdOf<T>, BalanceOf<T>, FeeDestination),
+        /// parameters. [round_id, project_hash, number of milestones]
+        MilestonesSet(u32, T::Hash, u32),
+        /// parameters. [round_id, project_hash, milestone index, amount]
+        MilestoneReleased(u32, T::Hash, u32, u128),
+        /// parameters. [round_id, project_hash, amount]
+        EscrowReclaimed(u32, T::Hash, u128),
+        /// parameters. [round_id]
+        VestingScheduleSet(u32),
+        /// parameters. [round_id, project_hash, amount]
+        VestedGrantClaimed(u32, T::Hash, u128),
+        /// parameters. [round_id, project_hash, unvested amount]
+        VestingRevoked(u32, T::Hash, u128),
+        /// parameters. [round_id, challenge period]
+        ChallengePeriodSet(u32, T::BlockNumber),
+        /// parameters. [round_id, project_hash, evidence]
+        ProjectFlagged(u32, T::Hash, T::Hash),
+        /// parameters. [round_id, project_hash, resolution]
+        FlagResolved(u32, T::Hash, FlagResolution),
+        /// parameters. [round_id]
+        RoundFinalized(u32),
+        /// parameters. [round_id, conviction period]
+        ConvictionPeriodSet(u32, T::BlockNumber),
+        /// parameters. [project_hash, who, number of ballots, conviction]
+        VotedWithConviction(T::Hash, T::AccountId, u128, Conviction),
+        /// parameters. [round_id, who, amount]
+        ConvictionUnlocked(u32, T::AccountId, u128),
+        /// parameters. [round_id, delegator, delegate, budget]
+        VotesDelegated(u32, T::AccountId, T::AccountId, u128),
+        /// parameters. [round_id, delegator]
+        VotesUndelegated(u32, T::AccountId),
+        /// parameters. [project_hash, delegate, delegator, number of ballots]
+        DelegateVoted(T::Hash, T::AccountId, T::AccountId, u128),
+        /// parameters. [project_hash, signer, relayer, number of ballots]
+        SignedVoteRelayed(T::Hash, T::AccountId, T::AccountId, u128),
+        /// parameters. [round_id, eligibility rule]
+        EligibilityRuleSet(u32, Option<EligibilityRuleOf<T>>),
+        /// parameters. [round_id, who]
+        EligibilityProved(u32, T::AccountId),
+        /// parameters. [round_id, voter limits]
+        VoterLimitsSet(u32, VoterLimits),
+        /// parameters. [round_id, currency_id, rate, none when no longer accepted]
+        RoundCurrencySet(u32, CurrencyIdOf<T>, Option<CurrencyRate>),
+        /// parameters. [template_id]
+        RoundTemplateCreated(u32),
+        /// parameters. [template_id]
+        RoundTemplateRemoved(u32),
+        /// parameters. [round_id]
+        RoundArchived(u32),
+        /// parameters. [round_id]
+        RoundPruned(u32),
+        /// parameters. [round_id, who, badge item]
+        BadgeClaimed(u32, T::AccountId, u32),
+        /// parameters. [round_id, who]
+        EligibilityUnlocked(u32, T::AccountId),
     }
 
     // Errors inform users that something went wrong.
@@ -197,10 +982,102 @@ pub mod pallet {
         DuplicateRound,
         MismatchingCurencyId,
         InsufficientReserveDora,
+        TooManySponsors,
+        SponsorshipTooSmall,
+        InvalidSponsorConditions,
+        ConflictingSponsorConditions,
+        SponsorshipNotExist,
+        AlreadyRefunded,
+        RoundIsOngoing,
+        NoFeesToSweep,
+        NotProjectOwner,
+        InvalidMilestones,
+        ProjectAlreadyVoted,
+        EscrowNotExist,
+        MilestoneNotExist,
+        MilestoneAlreadyReleased,
+        EscrowExpired,
+        EscrowNotExpired,
+        InvalidVestingSchedule,
+        ConflictingPayoutSchedule,
+        VestedGrantNotExist,
+        NothingToClaim,
+        VestingAlreadyRevoked,
+        RoundNotSettled,
+        NotInChallengePeriod,
+        ChallengePeriodNotOver,
+        ProjectAlreadyFlagged,
+        InsufficientBond,
+        FlagNotExist,
+        UnresolvedFlags,
+        ConvictionNotEnabled,
+        InsufficientBalanceToLock,
+        ConvictionLockNotExist,
+        ConvictionLockNotExpired,
+        DelegationNotExist,
+        NotDelegate,
+        DelegationBudgetExceeded,
+        InvalidSignature,
+        InvalidNonce,
+        VotePayloadExpired,
+        NotEligible,
+        NoEligibilitySnapshot,
+        EligibilitySnapshotPassed,
+        TemplateExisted,
+        TemplateNotExist,
+        ArchiveDelayNotOver,
+        PayoutsPending,
+        TooManyVotedProjects,
+        TooManyVoters,
+        TooManyProjects,
+        TooManyVotes,
+        TooManyBallots,
+        SpendingCapExceeded,
+        InvalidCostCurve,
+        InvalidMatchingMode,
+        InvalidChallengePeriod,
+        RoundAlreadyVoted,
+        InvalidRoundCurrency,
+        TooManyRoundCurrencies,
+        PriceNotAvailable,
+        NoBadgeToClaim,
+        BadgeAlreadyClaimed,
+        /// The witness given is smaller than the round
+        InvalidWitness,
+        EligibilityLockNotExist,
+        EligibilityLockNotExpired,
     }
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// Remove the detailed storage of the archived rounds with the weight left in the block
+        fn on_idle(_n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+            // looking up the next round and removing its single entries
+            let overhead = T::WeightInfo::prune_round(0);
+            let per_item = T::WeightInfo::prune_round(1)
+                .saturating_sub(overhead)
+                .max(1);
+            let mut used: Weight = 0;
+            loop {
+                if remaining_weight < used.saturating_add(overhead).saturating_add(per_item) {
+                    break;
+                }
+                used = used.saturating_add(overhead);
+                let round_id = match RoundsToPrune::<T>::iter_keys().next() {
+                    Some(round_id) => round_id,
+                    None => break,
+                };
+                let limit = (remaining_weight.saturating_sub(used) / per_item)
+                    .min(u32::MAX as Weight) as u32;
+                let (removed, done) = Self::prune_round(round_id, limit);
+                used = used.saturating_add(per_item.saturating_mul(removed as Weight));
+                if !done {
+                    break;
+                }
+            }
+            used
+        }
+    }
 
     // Dispatchable functions allows users to interact with the pallet and invoke state changes.
     // These functions materialize as "extrinsics", which are often compared to transactions.
@@ -220,53 +1097,100 @@ pub mod pallet {
             // This function will return an error if the extrinsic is not signed.
             // https://substrate.dev/docs/en/knowledgebase/runtime/origin
             let who = ensure_signed(origin)?;
-            ensure!(
-                Rounds::<T>::contains_key(&round_id),
-                Error::<T>::RoundNotExist
-            );
-            let round = Rounds::<T>::get(round_id).unwrap();
-            ensure!(true == round.ongoing, Error::<T>::RoundHasEnded);
-            ensure!(
-                currency_id == round.currency_id,
-                Error::<T>::MismatchingCurencyId
-            );
-            // the minimum unit, make sure the donate is greater than this
-            let min_unit_number = Self::cal_amount(1u128, false);
-            let amount_number = Self::balance_to_u128(amount);
-            let fee_number = T::FeeRatioPerVote::get()
-                .checked_mul(amount_number / T::NumberOfUnitPerVote::get())
-                .unwrap();
-            ensure!(
-                amount_number > min_unit_number,
-                Error::<T>::DonationTooSmall
-            );
-
-            let _ = T::MultiCurrency::transfer(
+            Self::do_donate(
+                who,
+                round_id,
+                amount,
                 currency_id,
-                &who,
-                &Self::account_id(),
-                Self::u128_to_balance(fee_number),
+                SponsorConditions::default(),
+                true,
             )?;
-            let _ = T::MultiCurrency::transfer(
-                currency_id,
-                &who,
-                &Self::round_admin_account(round_id),
-                Self::u128_to_balance(amount_number - fee_number),
+            Ok(().into())
+        }
+
+        /// Donate to the support pool of an `ongoing` round under some conditions, the donation
+        /// is refunded entirely if the conditions are not met when the round ends
+        #[pallet::weight(T::WeightInfo::sponsor())]
+        pub fn sponsor(
+            origin: OriginFor<T>,
+            round_id: u32,
+            #[pallet::compact] amount: BalanceOf<T>,
+            currency_id: CurrencyIdOf<T>,
+            conditions: SponsorConditions<T::BlockNumber>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            if let Some(expiry) = conditions.expiry {
+                ensure!(
+                    expiry > frame_system::Pallet::<T>::block_number(),
+                    Error::<T>::InvalidSponsorConditions
+                );
+            }
+            Self::do_donate(who, round_id, amount, currency_id, conditions, true)?;
+            Ok(().into())
+        }
+
+        /// Move treasury funds into the support pool of an `ongoing` round, the treasury is
+        /// recorded as a sponsor of the round and gets back its share of what is not distributed
+        #[pallet::weight(T::WeightInfo::fund_round_from_treasury())]
+        pub fn fund_round_from_treasury(
+            origin: OriginFor<T>,
+            round_id: u32,
+            #[pallet::compact] amount: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            T::TreasuryOrigin::ensure_origin(origin)?;
+            let round = Rounds::<T>::get(round_id).ok_or(Error::<T>::RoundNotExist)?;
+            // no fee is charged on treasury funds
+            Self::do_donate(
+                T::TreasuryAccount::get(),
+                round_id,
+                amount,
+                round.currency_id,
+                SponsorConditions::default(),
+                false,
             )?;
-            // add deposit to pallet account.
-            // update the round
-            Rounds::<T>::mutate(round_id, |rnd| match rnd {
-                Some(round) => {
-                    let ptsp = round.pre_tax_support_pool;
-                    let sp = round.support_pool;
-                    let tt = round.total_tax;
-                    round.pre_tax_support_pool = amount_number.checked_add(ptsp).unwrap();
-                    round.support_pool = (amount_number - fee_number).checked_add(sp).unwrap();
-                    round.total_tax = fee_number.checked_add(tt).unwrap();
-                }
-                _ => (),
-            });
-            Self::deposit_event(Event::DonateSucceed(round_id, who, amount));
+            Ok(().into())
+        }
+
+        /// Refund a sponsor of a round with its share of the support pool which was not
+        /// distributed, or its whole donation if its conditions were not met. Anyone can trigger
+        /// the refund once the round has ended, or once the sponsorship has expired.
+        #[pallet::weight(T::WeightInfo::refund_sponsor())]
+        pub fn refund_sponsor(
+            origin: OriginFor<T>,
+            round_id: u32,
+            sponsor: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            let mut round = Rounds::<T>::get(round_id).ok_or(Error::<T>::RoundNotExist)?;
+            let mut sponsorship = Sponsorships::<T>::get(round_id, &sponsor)
+                .ok_or(Error::<T>::SponsorshipNotExist)?;
+            ensure!(!sponsorship.refunded, Error::<T>::AlreadyRefunded);
+            if round.ongoing {
+                // an expired sponsorship leaves the pool before the end of the round
+                let now = frame_system::Pallet::<T>::block_number();
+                ensure!(
+                    sponsorship
+                        .conditions
+                        .expiry
+                        .map_or(false, |expiry| now > expiry),
+                    Error::<T>::RoundIsOngoing
+                );
+                sponsorship.void = true;
+                round.support_pool = round.support_pool.saturating_sub(sponsorship.amount);
+            } else {
+                ensure!(
+                    !SettlementDeadlines::<T>::contains_key(round_id),
+                    Error::<T>::RoundNotSettled
+                );
+            }
+            let refund = Self::sponsor_refund(&round, &sponsorship);
+            if refund > 0 {
+                Self::pay_from_round(round_id, &round, &sponsor, refund)?;
+            }
+            sponsorship.refunded = true;
+            Sponsorships::<T>::insert(round_id, &sponsor, sponsorship);
+            Rounds::<T>::insert(round_id, round);
+            Self::deposit_event(Event::SponsorRefunded(round_id, sponsor, refund));
             Ok(().into())
         }
 
@@ -298,26 +1222,95 @@ pub mod pallet {
                 .clone()
                 .try_into()
                 .map_err(|_| Error::<T>::BadMetadata)?;
-            let round = Round {
-                ongoing: true,
+            let template = RoundTemplate {
                 name: bounded_name,
-                currency_id: currency_id,
-                support_pool: 0,
-                pre_tax_support_pool: 0,
-                total_support_area: 0,
-                total_tax: 0,
-                round_reserve: round_reserve,
-                admin: admin.clone(),
+                currency_id,
+                admin,
+                round_reserve,
+                matching: MatchingMode::default(),
+                max_match_per_project: Perbill::one(),
+                eligibility: None,
+                limits: VoterLimits::default(),
+                cost_curve: CostCurve::default(),
+                challenge_period: Zero::zero(),
+                conviction_period: Zero::zero(),
             };
-            Rounds::<T>::insert(round_id, round);
-            Self::deposit_event(Event::RoundStarted(round_id));
+            Self::do_start_round(round_id, template)?;
+            Ok(().into())
+        }
+
+        /// Store the settings of recurring rounds under `template_id`
+        #[pallet::weight(T::WeightInfo::create_round_template())]
+        pub fn create_round_template(
+            origin: OriginFor<T>,
+            template_id: u32,
+            template: RoundTemplateOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            T::AdminOrigin::ensure_origin(origin)?;
+            Self::ensure_valid_template(&template)?;
+            ensure!(
+                !RoundTemplates::<T>::contains_key(template_id),
+                Error::<T>::TemplateExisted
+            );
+            RoundTemplates::<T>::insert(template_id, template);
+            Self::deposit_event(Event::RoundTemplateCreated(template_id));
+            Ok(().into())
+        }
+
+        #[pallet::weight(T::WeightInfo::remove_round_template())]
+        pub fn remove_round_template(
+            origin: OriginFor<T>,
+            template_id: u32,
+        ) -> DispatchResultWithPostInfo {
+            T::AdminOrigin::ensure_origin(origin)?;
+            ensure!(
+                RoundTemplates::<T>::contains_key(template_id),
+                Error::<T>::TemplateNotExist
+            );
+            RoundTemplates::<T>::remove(template_id);
+            Self::deposit_event(Event::RoundTemplateRemoved(template_id));
+            Ok(().into())
+        }
+
+        /// Start a round with the settings of a template
+        #[pallet::weight(T::WeightInfo::start_round_from_template())]
+        pub fn start_round_from_template(
+            origin: OriginFor<T>,
+            round_id: u32,
+            template_id: u32,
+        ) -> DispatchResultWithPostInfo {
+            T::AdminOrigin::ensure_origin(origin)?;
+            let template =
+                RoundTemplates::<T>::get(template_id).ok_or(Error::<T>::TemplateNotExist)?;
+            Self::do_start_round(round_id, template)?;
+            Ok(().into())
+        }
+
+        /// Start a round with the same settings as a previous round
+        #[pallet::weight(T::WeightInfo::clone_round())]
+        pub fn clone_round(
+            origin: OriginFor<T>,
+            round_id: u32,
+            prev_round_id: u32,
+        ) -> DispatchResultWithPostInfo {
+            T::AdminOrigin::ensure_origin(origin)?;
+            let template =
+                Self::template_of_round(prev_round_id).ok_or(Error::<T>::RoundNotExist)?;
+            Self::do_start_round(round_id, template)?;
             Ok(().into())
         }
 
         // TODO add funding grants, Reserve number/by round
         /// End an `ongoing` round and distribute the funds in sponsor pool, any invalid index or round status will cause errors
-        #[pallet::weight(T::WeightInfo::end_round())]
-        pub fn end_round(origin: OriginFor<T>, round_id: u32) -> DispatchResultWithPostInfo {
+        ///
+        /// The `witness` bounds the size of the round to weigh the call, it is given by
+        /// `round_witness`. The pairwise matching is only paid for by the rounds using it.
+        #[pallet::weight(Pallet::<T>::end_round_weight(witness, true))]
+        pub fn end_round(
+            origin: OriginFor<T>,
+            round_id: u32,
+            witness: RoundWitness,
+        ) -> DispatchResultWithPostInfo {
             // Only amdin can control the round
             T::AdminOrigin::ensure_origin(origin)?;
             ensure!(
@@ -326,39 +1319,74 @@ pub mod pallet {
             );
             let mut round = Rounds::<T>::get(round_id).unwrap();
             ensure!(true == round.ongoing, Error::<T>::RoundHasEnded);
-            let area = round.total_support_area;
-            let pool = round.support_pool;
-            // update the support fund
-            for (hash, _) in Projects::<T>::iter_prefix(round_id) {
-                if area > 0 {
-                    Projects::<T>::mutate(round_id, hash, |poj| match poj {
-                        Some(project) => {
-                            project.support_fund =
-                                project.support_area.checked_mul(pool / area).unwrap();
-                        }
-                        _ => (),
-                    });
+            let size = RoundSizes::<T>::get(round_id);
+            let sponsorships: Vec<_> = Sponsorships::<T>::iter_prefix(round_id).collect();
+            ensure!(
+                witness.projects >= size.projects
+                    && witness.votes >= size.votes
+                    && witness.voters_per_project >= size.voters_per_project
+                    && witness.participants >= size.participants
+                    && witness.sponsors as usize >= sponsorships.len(),
+                Error::<T>::InvalidWitness
+            );
+            // donations of sponsors whose conditions do not hold leave the support pool
+            let now = frame_system::Pallet::<T>::block_number();
+            let projects = size.projects;
+            let actual = RoundWitness {
+                projects,
+                votes: size.votes,
+                voters_per_project: size.voters_per_project,
+                participants: size.participants,
+                sponsors: sponsorships.len() as u32,
+            };
+            let pairwise = matches!(round.matching, MatchingMode::PairwiseBounded(_));
+            for (sponsor, mut sponsorship) in sponsorships {
+                if !sponsorship.void
+                    && !Self::sponsor_conditions_met(&sponsorship.conditions, projects, now)
+                {
+                    round.support_pool = round.support_pool.saturating_sub(sponsorship.amount);
+                    sponsorship.void = true;
+                    Sponsorships::<T>::insert(round_id, &sponsor, sponsorship);
                 }
             }
-            // unreserve the DORA to voters and update states
-            for (voter, _) in RoundParticipants::<T>::iter_prefix(round_id) {
-                let reserve_num = T::ReserveUnit::get()
-                    .checked_mul(round.round_reserve)
-                    .unwrap();
-                let reserve_balance = TryInto::<DoraBalance<T>>::try_into(reserve_num)
-                    .ok()
-                    .unwrap();
-                // ReservableCurrency::unreserve does not fail (it will lock up as much as amount)
-                T::Currency::unreserve(&voter, reserve_balance);
+            // update the support fund
+            let mut distributed = 0u128;
+            for (hash, support_fund) in Self::support_funds(round_id, &round) {
+                distributed = distributed.saturating_add(support_fund);
+                Projects::<T>::mutate(round_id, hash, |poj| match poj {
+                    Some(project) => {
+                        project.support_fund = support_fund;
+                    }
+                    _ => (),
+                });
+            }
+            // what is left is refunded to the sponsors
+            round.undistributed_pool = round.support_pool.saturating_sub(distributed);
+            // with a challenge period, the matching is paid once the round is finalized
+            let challenge_period = ChallengePeriods::<T>::get(round_id);
+            let settled = challenge_period.is_zero();
+            if settled {
+                Self::settle_round(round_id, &round)?;
+            } else {
+                SettlementDeadlines::<T>::insert(round_id, now.saturating_add(challenge_period));
+            }
+            // unreserve the DORA to voters and update states, the voters are kept in
+            // `RoundParticipants` to claim their badge
+            for (voter, reserved) in RoundReserves::<T>::drain_prefix(round_id) {
+                let reserve_balance = TryInto::<DoraBalance<T>>::try_into(reserved).ok().unwrap();
+                // only what this round reserved is released, other reserves are left untouched
+                T::Currency::unreserve_named(&RESERVE_ID, &voter, reserve_balance);
             }
-            // Maybe this is unnecessary as round ended, voter can not do malicious attack
-            let _ = RoundParticipants::<T>::clear_prefix(round_id, u32::MAX, None);
-
             // update round status
             round.ongoing = false;
+            RoundEndedAt::<T>::insert(round_id, now);
             Rounds::<T>::insert(round_id, round);
+            // the results are only final once the challenge period is over
+            if settled {
+                T::OnRoundEnded::on_round_ended(round_id, &Self::project_results(round_id));
+            }
             Self::deposit_event(Event::RoundEnded(round_id));
-            Ok(().into())
+            Ok(Some(Self::end_round_weight(&actual, pairwise)).into())
         }
 
         /// Register a project in an ongoing round, so that it can be voted
@@ -370,167 +1398,942 @@ pub mod pallet {
             name: Vec<u8>,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
+            Self::do_register_project(who, round_id, hash, name)?;
+            Ok(().into())
+        }
+
+        /// Vote to a project, this function will transfer corresponding amount of token per your input ballot
+        #[pallet::weight(T::WeightInfo::vote())]
+        pub fn vote(
+            origin: OriginFor<T>,
+            currency_id: CurrencyIdOf<T>,
+            round_id: u32,
+            hash: T::Hash,
+            ballot: u128,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::do_vote(who, currency_id, round_id, hash, ballot, Conviction::None)?;
+            Ok(().into())
+        }
+
+        /// Select the formula used to match the projects of an `ongoing` round, the support area
+        /// is only used with the quadratic cost curve
+        #[pallet::weight(T::WeightInfo::set_matching_mode())]
+        pub fn set_matching_mode(
+            origin: OriginFor<T>,
+            round_id: u32,
+            mode: MatchingMode,
+        ) -> DispatchResultWithPostInfo {
+            T::AdminOrigin::ensure_origin(origin)?;
+            Self::ensure_valid_matching(&mode)?;
+            Rounds::<T>::try_mutate(round_id, |rnd| -> DispatchResult {
+                let round = rnd.as_mut().ok_or(Error::<T>::RoundNotExist)?;
+                ensure!(round.ongoing, Error::<T>::RoundHasEnded);
+                if let MatchingMode::PairwiseBounded(_) = mode {
+                    ensure!(
+                        RoundSizes::<T>::get(round_id).voters_per_project
+                            <= T::MaxVotersPerProject::get(),
+                        Error::<T>::TooManyVoters
+                    );
+                }
+                ensure!(
+                    Self::matching_fits_cost_curve(&mode, &round.cost_curve),
+                    Error::<T>::InvalidMatchingMode
+                );
+                round.matching = mode;
+                Ok(())
+            })?;
+            Self::deposit_event(Event::MatchingModeSet(round_id, mode));
+            Ok(().into())
+        }
+
+        /// Select how ballots are priced in an `ongoing` round, only before anyone voted. The
+        /// rounds matched by the support area keep the quadratic curve.
+        #[pallet::weight(T::WeightInfo::set_cost_curve())]
+        pub fn set_cost_curve(
+            origin: OriginFor<T>,
+            round_id: u32,
+            curve: CostCurve,
+        ) -> DispatchResultWithPostInfo {
+            T::AdminOrigin::ensure_origin(origin)?;
+            Self::ensure_valid_cost_curve(&curve)?;
+            Rounds::<T>::try_mutate(round_id, |rnd| -> DispatchResult {
+                let round = rnd.as_mut().ok_or(Error::<T>::RoundNotExist)?;
+                ensure!(round.ongoing, Error::<T>::RoundHasEnded);
+                ensure!(
+                    ProjectVotes::<T>::iter_prefix((round_id,)).next().is_none(),
+                    Error::<T>::RoundAlreadyVoted
+                );
+                ensure!(
+                    Self::matching_fits_cost_curve(&round.matching, &curve),
+                    Error::<T>::InvalidCostCurve
+                );
+                round.cost_curve = curve;
+                Ok(())
+            })?;
+            Self::deposit_event(Event::CostCurveSet(round_id, curve));
+            Ok(().into())
+        }
+
+        /// Cap the share of the support pool a single project of an `ongoing` round can receive,
+        /// the excess is shared between the other projects when the round ends
+        #[pallet::weight(T::WeightInfo::set_max_match_per_project())]
+        pub fn set_max_match_per_project(
+            origin: OriginFor<T>,
+            round_id: u32,
+            max_match: Perbill,
+        ) -> DispatchResultWithPostInfo {
+            T::AdminOrigin::ensure_origin(origin)?;
+            Rounds::<T>::try_mutate(round_id, |rnd| -> DispatchResult {
+                let round = rnd.as_mut().ok_or(Error::<T>::RoundNotExist)?;
+                ensure!(round.ongoing, Error::<T>::RoundHasEnded);
+                round.max_match_per_project = max_match;
+                Ok(())
+            })?;
+            Self::deposit_event(Event::MaxMatchPerProjectSet(round_id, max_match));
+            Ok(().into())
+        }
+
+        /// Only let the holders of a currency vote in an `ongoing` round, none opens it to
+        /// everyone again
+        #[pallet::weight(T::WeightInfo::set_eligibility_rule())]
+        pub fn set_eligibility_rule(
+            origin: OriginFor<T>,
+            round_id: u32,
+            rule: Option<EligibilityRuleOf<T>>,
+        ) -> DispatchResultWithPostInfo {
+            T::AdminOrigin::ensure_origin(origin)?;
+            Rounds::<T>::try_mutate(round_id, |rnd| -> DispatchResult {
+                let round = rnd.as_mut().ok_or(Error::<T>::RoundNotExist)?;
+                ensure!(round.ongoing, Error::<T>::RoundHasEnded);
+                round.eligibility = rule.clone();
+                Ok(())
+            })?;
+            Self::deposit_event(Event::EligibilityRuleSet(round_id, rule));
+            Ok(().into())
+        }
+
+        /// Limit how much a single voter can spend and how many ballots it can give to a
+        /// project in an `ongoing` round
+        #[pallet::weight(T::WeightInfo::set_voter_limits())]
+        pub fn set_voter_limits(
+            origin: OriginFor<T>,
+            round_id: u32,
+            limits: VoterLimits,
+        ) -> DispatchResultWithPostInfo {
+            T::AdminOrigin::ensure_origin(origin)?;
+            Rounds::<T>::try_mutate(round_id, |rnd| -> DispatchResult {
+                let round = rnd.as_mut().ok_or(Error::<T>::RoundNotExist)?;
+                ensure!(round.ongoing, Error::<T>::RoundHasEnded);
+                round.limits = limits.clone();
+                Ok(())
+            })?;
+            Self::deposit_event(Event::VoterLimitsSet(round_id, limits));
+            Ok(().into())
+        }
+
+        /// Accept another currency for the votes and donations of an `ongoing` round at the
+        /// given rate, none stops accepting it. What was already paid in it is still paid out.
+        #[pallet::weight(T::WeightInfo::set_round_currency())]
+        pub fn set_round_currency(
+            origin: OriginFor<T>,
+            round_id: u32,
+            currency_id: CurrencyIdOf<T>,
+            rate: Option<CurrencyRate>,
+        ) -> DispatchResultWithPostInfo {
+            T::AdminOrigin::ensure_origin(origin)?;
+            let round = Rounds::<T>::get(round_id).ok_or(Error::<T>::RoundNotExist)?;
+            ensure!(round.ongoing, Error::<T>::RoundHasEnded);
+            ensure!(
+                currency_id != round.currency_id,
+                Error::<T>::InvalidRoundCurrency
+            );
+            match rate {
+                Some(rate) => {
+                    ensure!(
+                        rate != CurrencyRate::Fixed(FixedU128::zero()),
+                        Error::<T>::InvalidRoundCurrency
+                    );
+                    ensure!(
+                        RoundCurrencies::<T>::contains_key(round_id, &currency_id)
+                            || (RoundCurrencies::<T>::iter_prefix(round_id).count() as u32)
+                                < T::MaxRoundCurrencies::get(),
+                        Error::<T>::TooManyRoundCurrencies
+                    );
+                    RoundCurrencies::<T>::insert(round_id, &currency_id, rate);
+                }
+                None => RoundCurrencies::<T>::remove(round_id, &currency_id),
+            }
+            Self::deposit_event(Event::RoundCurrencySet(round_id, currency_id, rate));
+            Ok(().into())
+        }
+
+        /// Record that the caller holds enough to vote in a token-gated round with a snapshot,
+        /// until the snapshot block. The holdings are locked until the snapshot, so that they
+        /// can not be passed to another account to prove it eligible too.
+        #[pallet::weight(T::WeightInfo::prove_eligibility())]
+        pub fn prove_eligibility(
+            origin: OriginFor<T>,
+            round_id: u32,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let round = Rounds::<T>::get(round_id).ok_or(Error::<T>::RoundNotExist)?;
+            ensure!(round.ongoing, Error::<T>::RoundHasEnded);
+            let rule = round.eligibility.ok_or(Error::<T>::NoEligibilitySnapshot)?;
+            let snapshot = rule.snapshot.ok_or(Error::<T>::NoEligibilitySnapshot)?;
             ensure!(
-                name.len() >= T::NameMinLength::get().try_into().unwrap(),
-                Error::<T>::ProjectNameTooShort
+                frame_system::Pallet::<T>::block_number() <= snapshot,
+                Error::<T>::EligibilitySnapshotPassed
             );
+            // locks only keep the free balance
             ensure!(
-                name.len() <= T::NameMaxLength::get().try_into().unwrap(),
-                Error::<T>::ProjectNameTooLong
+                Self::balance_to_u128(T::MultiCurrency::free_balance(rule.currency_id, &who))
+                    >= rule.min_balance,
+                Error::<T>::NotEligible
             );
+            if let Some(currency_id) = EligibilityLocks::<T>::get(round_id, &who) {
+                T::MultiCurrency::remove_lock(
+                    Self::eligibility_lock_id(round_id),
+                    currency_id,
+                    &who,
+                )?;
+            }
+            T::MultiCurrency::set_lock(
+                Self::eligibility_lock_id(round_id),
+                rule.currency_id,
+                &who,
+                Self::u128_to_balance(rule.min_balance),
+            )?;
+            EligibilityLocks::<T>::insert(round_id, &who, rule.currency_id);
+            EligibleVoters::<T>::insert(round_id, &who, true);
+            Self::deposit_event(Event::EligibilityProved(round_id, who));
+            Ok(().into())
+        }
+
+        /// Replace an ended round and its projects with summaries once all its payouts are done
+        /// and `ArchiveDelay` passed, anyone can trigger it. The rest of the storage of the round
+        /// is removed in `on_idle`.
+        #[pallet::weight(T::WeightInfo::archive_round())]
+        pub fn archive_round(origin: OriginFor<T>, round_id: u32) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            let round = Rounds::<T>::get(round_id).ok_or(Error::<T>::RoundNotExist)?;
+            ensure!(!round.ongoing, Error::<T>::RoundIsOngoing);
             ensure!(
-                Rounds::<T>::contains_key(&round_id),
-                Error::<T>::RoundNotExist
+                !SettlementDeadlines::<T>::contains_key(round_id),
+                Error::<T>::RoundNotSettled
             );
-            let round = Rounds::<T>::get(round_id).unwrap();
-            ensure!(true == round.ongoing, Error::<T>::RoundHasEnded);
+            let ended_at = RoundEndedAt::<T>::get(round_id).unwrap_or_default();
             ensure!(
-                !Projects::<T>::contains_key(&round_id, &hash),
-                Error::<T>::DuplicateProject
+                frame_system::Pallet::<T>::block_number()
+                    >= ended_at.saturating_add(T::ArchiveDelay::get()),
+                Error::<T>::ArchiveDelayNotOver
             );
-
-            let bounded_name: BoundedVec<u8, T::NameMaxLength> = name
-                .clone()
-                .try_into()
-                .map_err(|_| Error::<T>::BadMetadata)?;
-            let project = Project {
-                total_votes: 0,
-                grants: 0,
-                support_area: 0,
-                support_fund: 0,
-                name: bounded_name,
-                owner: who.clone(),
+            ensure!(
+                Self::payouts_done(round_id, &round),
+                Error::<T>::PayoutsPending
+            );
+            let summary = RoundSummary {
+                name: round.name,
+                currency_id: round.currency_id,
+                admin: round.admin,
+                matching: round.matching,
+                support_pool: round.support_pool,
+                total_tax: round.total_tax,
+                undistributed_pool: round.undistributed_pool,
+                ended_at,
             };
-            Projects::<T>::insert(round_id, hash, project);
-            Self::deposit_event(Event::ProjectRegistered(hash, who));
+            ArchivedRounds::<T>::insert(round_id, summary);
+            Rounds::<T>::remove(round_id);
+            RoundsToPrune::<T>::insert(round_id, ());
+            Self::deposit_event(Event::RoundArchived(round_id));
             Ok(().into())
         }
 
-        /// Vote to a project, this function will transfer corresponding amount of token per your input ballot
-        #[pallet::weight(T::WeightInfo::vote())]
-        pub fn vote(
+        /// Split the funds of a project into milestones, the funds are then escrowed and released
+        /// milestone by milestone. Milestones can only be set before the project is voted.
+        #[pallet::weight(T::WeightInfo::set_milestones(shares.len() as u32))]
+        pub fn set_milestones(
             origin: OriginFor<T>,
-            currency_id: CurrencyIdOf<T>,
             round_id: u32,
             hash: T::Hash,
-            ballot: u128,
+            shares: Vec<Perbill>,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
-            // check whether this round still ongoing
+            let round = Rounds::<T>::get(round_id).ok_or(Error::<T>::RoundNotExist)?;
+            ensure!(round.ongoing, Error::<T>::RoundHasEnded);
+            let project = Projects::<T>::get(round_id, &hash).ok_or(Error::<T>::ProjectNotExist)?;
+            ensure!(project.owner == who, Error::<T>::NotProjectOwner);
+            ensure!(project.total_votes == 0, Error::<T>::ProjectAlreadyVoted);
             ensure!(
-                Rounds::<T>::contains_key(&round_id),
-                Error::<T>::RoundNotExist
+                !RoundVesting::<T>::contains_key(round_id),
+                Error::<T>::ConflictingPayoutSchedule
             );
-            let round = Rounds::<T>::get(round_id).unwrap();
-            ensure!(true == round.ongoing, Error::<T>::RoundHasEnded);
+            // the shares must add up to the whole funds
+            let total = shares
+                .iter()
+                .try_fold(0u32, |acc, share| acc.checked_add(share.deconstruct()));
             ensure!(
-                Projects::<T>::contains_key(&round_id, &hash),
-                Error::<T>::ProjectNotExist
+                !shares.iter().any(|share| share.is_zero())
+                    && total == Some(Perbill::one().deconstruct()),
+                Error::<T>::InvalidMilestones
             );
+            let milestones: BoundedVec<Milestone, T::MaxMilestones> = shares
+                .into_iter()
+                .map(|share| Milestone {
+                    share,
+                    released: false,
+                })
+                .collect::<Vec<_>>()
+                .try_into()
+                .map_err(|_| Error::<T>::InvalidMilestones)?;
+            let count = milestones.len() as u32;
+            Milestones::<T>::insert(round_id, hash, milestones);
+            Self::deposit_event(Event::MilestonesSet(round_id, hash, count));
+            Ok(().into())
+        }
+
+        /// Approve a milestone of a project and release its tranche to the project owner, either
+        /// by the round admin or the reviewer origin
+        #[pallet::weight(T::WeightInfo::approve_milestone())]
+        pub fn approve_milestone(
+            origin: OriginFor<T>,
+            round_id: u32,
+            hash: T::Hash,
+            index: u32,
+        ) -> DispatchResultWithPostInfo {
+            let round = Rounds::<T>::get(round_id).ok_or(Error::<T>::RoundNotExist)?;
+            if T::MilestoneReviewerOrigin::try_origin(origin.clone()).is_err() {
+                let who = ensure_signed(origin)?;
+                ensure!(who == round.admin, DispatchError::BadOrigin);
+            }
+            let mut escrow =
+                Escrows::<T>::get(round_id, &hash).ok_or(Error::<T>::EscrowNotExist)?;
             ensure!(
-                currency_id == round.currency_id,
-                Error::<T>::MismatchingCurencyId
+                frame_system::Pallet::<T>::block_number() <= escrow.deadline,
+                Error::<T>::EscrowExpired
             );
-            ensure!(ballot > 0, Error::<T>::InvalidBallot);
-
-            // need to calculate hash of project hash and round_id combination here to avoid conflicts of projects in different rounds
-            let vote_hash = T::Hashing::hash_of(&(&hash, &round_id));
-            let voted = match ProjectVotes::<T>::get(vote_hash, &who) {
-                Some(val) => val,
-                None => 0,
+            let mut milestones =
+                Milestones::<T>::get(round_id, &hash).ok_or(Error::<T>::EscrowNotExist)?;
+            let milestone = milestones
+                .get_mut(index as usize)
+                .ok_or(Error::<T>::MilestoneNotExist)?;
+            ensure!(!milestone.released, Error::<T>::MilestoneAlreadyReleased);
+            milestone.released = true;
+            // the last milestone releases what is left, so that no dust stays in escrow
+            let amount = if milestones.iter().all(|milestone| milestone.released) {
+                escrow.total.saturating_sub(escrow.released)
+            } else {
+                milestones[index as usize].share * escrow.total
             };
-
-            // check whether staked
-            let staked = RoundParticipants::<T>::get(round_id, &who);
-            if round.round_reserve > 0 && staked == None {
-                let reserve_num = T::ReserveUnit::get()
-                    .checked_mul(round.round_reserve)
-                    .unwrap();
-                let reserve_balance = TryInto::<DoraBalance<T>>::try_into(reserve_num)
-                    .ok()
-                    .unwrap();
-                T::Currency::reserve(&who, reserve_balance)
-                    .map_err(|_| Error::<T>::InsufficientReserveDora)?;
-                RoundParticipants::<T>::insert(round_id, &who, true);
+            let project = Projects::<T>::get(round_id, &hash).ok_or(Error::<T>::ProjectNotExist)?;
+            if amount > 0 {
+                Self::pay_from_round(round_id, &round, &project.owner, amount)?;
             }
-            let cost = Self::cal_cost(voted.clone(), ballot);
-            let amount = Self::cal_amount(cost, false);
-            let fee = Self::cal_amount(cost, true);
-
-            let _ = T::MultiCurrency::transfer(
-                currency_id,
-                &who,
-                &Self::account_id(),
-                Self::u128_to_balance(fee),
-            )?;
-            // transfer first, update last, as transfer will ensure the free balance is enough
-            let _ = T::MultiCurrency::transfer(
-                currency_id,
-                &who,
-                &Self::round_admin_account(round_id),
-                Self::u128_to_balance(amount - fee),
-            )?;
-            // update the project and corresponding round
-            ProjectVotes::<T>::insert(vote_hash, &who, ballot + voted);
-            Projects::<T>::mutate(round_id, hash, |poj| {
-                match poj {
-                    Some(project) => {
-                        let support_area = ballot.checked_mul(project.total_votes - voted).unwrap();
-                        project.support_area =
-                            support_area.checked_add(project.support_area).unwrap();
-                        project.total_votes += ballot;
-                        project.grants += amount - fee;
-                        //debug::info!("Total votes: {:?}, Current votes: {:?}, Support Area: {:?},Est cost: {:?}",
-                        // poj.total_votes, voted, support_area, cost);
-                        Rounds::<T>::mutate(round_id, |rnd| match rnd {
-                            Some(round) => {
-                                let tsa = round.total_support_area;
-                                let tt = round.total_tax;
-                                round.total_support_area = support_area.checked_add(tsa).unwrap();
-                                round.total_tax = fee.checked_add(tt).unwrap();
-                            }
-                            _ => (),
-                        });
-                    }
-                    _ => (),
-                }
-            });
-            Self::deposit_event(Event::VoteSucceed(hash, who, ballot));
+            escrow.released = escrow.released.saturating_add(amount);
+            Escrows::<T>::insert(round_id, hash, escrow);
+            Milestones::<T>::insert(round_id, hash, milestones);
+            Self::deposit_event(Event::MilestoneReleased(round_id, hash, index, amount));
             Ok(().into())
         }
-    }
-}
 
-impl<T: Config> Pallet<T> {
-    // Add public immutables and private mutables.
+        /// Send the funds of a project which were not released before the deadline to the
+        /// treasury, anyone can trigger it
+        #[pallet::weight(T::WeightInfo::reclaim_escrow())]
+        pub fn reclaim_escrow(
+            origin: OriginFor<T>,
+            round_id: u32,
+            hash: T::Hash,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            let round = Rounds::<T>::get(round_id).ok_or(Error::<T>::RoundNotExist)?;
+            let escrow = Escrows::<T>::get(round_id, &hash).ok_or(Error::<T>::EscrowNotExist)?;
+            ensure!(
+                frame_system::Pallet::<T>::block_number() > escrow.deadline,
+                Error::<T>::EscrowNotExpired
+            );
+            let amount = escrow.total.saturating_sub(escrow.released);
+            if amount > 0 {
+                Self::pay_from_round(round_id, &round, &T::TreasuryAccount::get(), amount)?;
+            }
+            Escrows::<T>::remove(round_id, &hash);
+            Self::deposit_event(Event::EscrowReclaimed(round_id, hash, amount));
+            Ok(().into())
+        }
 
-    pub fn account_id() -> T::AccountId {
-        T::PalletId::get().into_account_truncating()
-    }
+        /// Pay the projects of an `ongoing` round through a linear vesting schedule instead of
+        /// paying them through the round admin. It can only be set before the round is voted.
+        #[pallet::weight(T::WeightInfo::set_vesting_schedule())]
+        pub fn set_vesting_schedule(
+            origin: OriginFor<T>,
+            round_id: u32,
+            schedule: VestingSchedule<T::BlockNumber>,
+        ) -> DispatchResultWithPostInfo {
+            T::AdminOrigin::ensure_origin(origin)?;
+            let round = Rounds::<T>::get(round_id).ok_or(Error::<T>::RoundNotExist)?;
+            ensure!(round.ongoing, Error::<T>::RoundHasEnded);
+            ensure!(
+                !schedule.duration.is_zero() && schedule.cliff <= schedule.duration,
+                Error::<T>::InvalidVestingSchedule
+            );
+            ensure!(
+                Milestones::<T>::iter_key_prefix(round_id).next().is_none(),
+                Error::<T>::ConflictingPayoutSchedule
+            );
+            // grants already paid to the admin can not be vested
+            ensure!(
+                Projects::<T>::iter_prefix_values(round_id).all(|project| project.total_votes == 0),
+                Error::<T>::ProjectAlreadyVoted
+            );
+            RoundVesting::<T>::insert(round_id, schedule);
+            Self::deposit_event(Event::VestingScheduleSet(round_id));
+            Ok(().into())
+        }
 
-    /// get corresponding accounts
+        /// Claim the vested funds of a project, by the project owner
+        #[pallet::weight(T::WeightInfo::claim_vested())]
+        pub fn claim_vested(
+            origin: OriginFor<T>,
+            round_id: u32,
+            hash: T::Hash,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let round = Rounds::<T>::get(round_id).ok_or(Error::<T>::RoundNotExist)?;
+            let project = Projects::<T>::get(round_id, &hash).ok_or(Error::<T>::ProjectNotExist)?;
+            ensure!(project.owner == who, Error::<T>::NotProjectOwner);
+            let mut grant =
+                VestedGrants::<T>::get(round_id, &hash).ok_or(Error::<T>::VestedGrantNotExist)?;
+            let amount = Self::vested_amount(round_id, &grant).saturating_sub(grant.claimed);
+            ensure!(amount > 0, Error::<T>::NothingToClaim);
+            Self::pay_from_round(round_id, &round, &who, amount)?;
+            grant.claimed = grant.claimed.saturating_add(amount);
+            VestedGrants::<T>::insert(round_id, hash, grant);
+            Self::deposit_event(Event::VestedGrantClaimed(round_id, hash, amount));
+            Ok(().into())
+        }
+
+        /// Stop the vesting of an abandoned project, what is not vested yet goes to the treasury.
+        /// The project owner can still claim what was vested.
+        #[pallet::weight(T::WeightInfo::revoke_vesting())]
+        pub fn revoke_vesting(
+            origin: OriginFor<T>,
+            round_id: u32,
+            hash: T::Hash,
+        ) -> DispatchResultWithPostInfo {
+            let round = Rounds::<T>::get(round_id).ok_or(Error::<T>::RoundNotExist)?;
+            if T::AdminOrigin::try_origin(origin.clone()).is_err() {
+                let who = ensure_signed(origin)?;
+                ensure!(who == round.admin, DispatchError::BadOrigin);
+            }
+            let mut grant =
+                VestedGrants::<T>::get(round_id, &hash).ok_or(Error::<T>::VestedGrantNotExist)?;
+            ensure!(!grant.revoked, Error::<T>::VestingAlreadyRevoked);
+            let vested = Self::vested_amount(round_id, &grant);
+            let unvested = grant.total.saturating_sub(vested);
+            if unvested > 0 {
+                Self::pay_from_round(round_id, &round, &T::TreasuryAccount::get(), unvested)?;
+            }
+            grant.total = vested;
+            grant.revoked = true;
+            VestedGrants::<T>::insert(round_id, hash, grant);
+            Self::deposit_event(Event::VestingRevoked(round_id, hash, unvested));
+            Ok(().into())
+        }
+
+        /// Let projects be flagged during `period` blocks after the end of an `ongoing` round, the
+        /// matching is paid once this period is over
+        #[pallet::weight(T::WeightInfo::set_challenge_period())]
+        pub fn set_challenge_period(
+            origin: OriginFor<T>,
+            round_id: u32,
+            period: T::BlockNumber,
+        ) -> DispatchResultWithPostInfo {
+            T::AdminOrigin::ensure_origin(origin)?;
+            Self::ensure_valid_challenge_period(period)?;
+            let round = Rounds::<T>::get(round_id).ok_or(Error::<T>::RoundNotExist)?;
+            ensure!(round.ongoing, Error::<T>::RoundHasEnded);
+            ChallengePeriods::<T>::insert(round_id, period);
+            Self::deposit_event(Event::ChallengePeriodSet(round_id, period));
+            Ok(().into())
+        }
+
+        /// Flag a project of a round in its challenge period, its matching is frozen until the
+        /// flag is resolved. Flagging requires a bond unless it comes from the challenge origin.
+        #[pallet::weight(T::WeightInfo::flag_project())]
+        pub fn flag_project(
+            origin: OriginFor<T>,
+            round_id: u32,
+            hash: T::Hash,
+            evidence: T::Hash,
+        ) -> DispatchResultWithPostInfo {
+            let deadline =
+                SettlementDeadlines::<T>::get(round_id).ok_or(Error::<T>::NotInChallengePeriod)?;
+            ensure!(
+                frame_system::Pallet::<T>::block_number() <= deadline,
+                Error::<T>::NotInChallengePeriod
+            );
+            ensure!(
+                Projects::<T>::contains_key(round_id, &hash),
+                Error::<T>::ProjectNotExist
+            );
+            ensure!(
+                !Flags::<T>::contains_key(round_id, &hash),
+                Error::<T>::ProjectAlreadyFlagged
+            );
+            let flag = match T::ChallengeOrigin::try_origin(origin) {
+                Ok(_) => Flag {
+                    challenger: None,
+                    bond: 0,
+                    evidence,
+                },
+                Err(origin) => {
+                    let who = ensure_signed(origin)?;
+                    let bond = T::ChallengeBond::get();
+                    let bond_balance = TryInto::<DoraBalance<T>>::try_into(bond).ok().unwrap();
+                    T::Currency::reserve_named(&RESERVE_ID, &who, bond_balance)
+                        .map_err(|_| Error::<T>::InsufficientBond)?;
+                    Flag {
+                        challenger: Some(who),
+                        bond,
+                        evidence,
+                    }
+                }
+            };
+            Flags::<T>::insert(round_id, hash, flag);
+            Self::deposit_event(Event::ProjectFlagged(round_id, hash, evidence));
+            Ok(().into())
+        }
+
+        /// Resolve the flag of a project, either restoring its matching or sharing it between
+        /// the other projects of the round. The bond is returned when the flag is upheld and
+        /// slashed to the treasury otherwise.
+        #[pallet::weight(T::WeightInfo::resolve_flag())]
+        pub fn resolve_flag(
+            origin: OriginFor<T>,
+            round_id: u32,
+            hash: T::Hash,
+            resolution: FlagResolution,
+        ) -> DispatchResultWithPostInfo {
+            T::ResolutionOrigin::ensure_origin(origin)?;
+            let flag = Flags::<T>::take(round_id, &hash).ok_or(Error::<T>::FlagNotExist)?;
+            if resolution == FlagResolution::Redistribute {
+                Self::redistribute_matching(round_id, hash)?;
+            }
+            if let Some(challenger) = flag.challenger {
+                let bond = TryInto::<DoraBalance<T>>::try_into(flag.bond).ok().unwrap();
+                match resolution {
+                    FlagResolution::Restore => {
+                        T::Currency::repatriate_reserved_named(
+                            &RESERVE_ID,
+                            &challenger,
+                            &T::TreasuryAccount::get(),
+                            bond,
+                            BalanceStatus::Free,
+                        )?;
+                    }
+                    FlagResolution::Redistribute => {
+                        T::Currency::unreserve_named(&RESERVE_ID, &challenger, bond);
+                    }
+                }
+            }
+            Self::deposit_event(Event::FlagResolved(round_id, hash, resolution));
+            Ok(().into())
+        }
+
+        /// Pay the matching of a round once its challenge period is over and all the flags are
+        /// resolved, anyone can trigger it. `projects` bounds the projects of the round to weigh
+        /// the call.
+        #[pallet::weight(T::WeightInfo::finalize_round(*projects))]
+        pub fn finalize_round(
+            origin: OriginFor<T>,
+            round_id: u32,
+            projects: u32,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            ensure!(
+                projects >= RoundSizes::<T>::get(round_id).projects,
+                Error::<T>::InvalidWitness
+            );
+            let round = Rounds::<T>::get(round_id).ok_or(Error::<T>::RoundNotExist)?;
+            let deadline =
+                SettlementDeadlines::<T>::get(round_id).ok_or(Error::<T>::NotInChallengePeriod)?;
+            ensure!(
+                frame_system::Pallet::<T>::block_number() > deadline,
+                Error::<T>::ChallengePeriodNotOver
+            );
+            ensure!(
+                Flags::<T>::iter_key_prefix(round_id).next().is_none(),
+                Error::<T>::UnresolvedFlags
+            );
+            Self::settle_round(round_id, &round)?;
+            SettlementDeadlines::<T>::remove(round_id);
+            T::OnRoundEnded::on_round_ended(round_id, &Self::project_results(round_id));
+            Self::deposit_event(Event::RoundFinalized(round_id));
+            Ok(().into())
+        }
+
+        /// Let voters of an `ongoing` round boost their ballots by locking funds for multiples of
+        /// `period` blocks after the end of the round, zero disables it
+        #[pallet::weight(T::WeightInfo::set_conviction_period())]
+        pub fn set_conviction_period(
+            origin: OriginFor<T>,
+            round_id: u32,
+            period: T::BlockNumber,
+        ) -> DispatchResultWithPostInfo {
+            T::AdminOrigin::ensure_origin(origin)?;
+            let round = Rounds::<T>::get(round_id).ok_or(Error::<T>::RoundNotExist)?;
+            ensure!(round.ongoing, Error::<T>::RoundHasEnded);
+            ConvictionPeriods::<T>::insert(round_id, period);
+            Self::deposit_event(Event::ConvictionPeriodSet(round_id, period));
+            Ok(().into())
+        }
+
+        /// Vote to a project like `vote`, the ballots count `conviction` times towards the
+        /// matching and as much as was paid is locked according to the conviction
+        #[pallet::weight(T::WeightInfo::vote_with_conviction())]
+        pub fn vote_with_conviction(
+            origin: OriginFor<T>,
+            currency_id: CurrencyIdOf<T>,
+            round_id: u32,
+            hash: T::Hash,
+            ballot: u128,
+            conviction: Conviction,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::do_vote(who.clone(), currency_id, round_id, hash, ballot, conviction)?;
+            Self::deposit_event(Event::VotedWithConviction(hash, who, ballot, conviction));
+            Ok(().into())
+        }
+
+        /// Remove the conviction lock of a voter of a round once it expired, anyone can trigger
+        /// it
+        #[pallet::weight(T::WeightInfo::unlock_conviction())]
+        pub fn unlock_conviction(
+            origin: OriginFor<T>,
+            round_id: u32,
+            who: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            let round = Rounds::<T>::get(round_id).ok_or(Error::<T>::RoundNotExist)?;
+            let lock = ConvictionLocks::<T>::get(round_id, &who)
+                .ok_or(Error::<T>::ConvictionLockNotExist)?;
+            let ended_at =
+                RoundEndedAt::<T>::get(round_id).ok_or(Error::<T>::ConvictionLockNotExpired)?;
+            let unlock_at = ended_at.saturating_add(
+                ConvictionPeriods::<T>::get(round_id)
+                    .saturating_mul(lock.conviction.lock_periods().into()),
+            );
+            ensure!(
+                frame_system::Pallet::<T>::block_number() >= unlock_at,
+                Error::<T>::ConvictionLockNotExpired
+            );
+            T::MultiCurrency::remove_lock(
+                Self::conviction_lock_id(round_id),
+                round.currency_id,
+                &who,
+            )?;
+            ConvictionLocks::<T>::remove(round_id, &who);
+            Self::deposit_event(Event::ConvictionUnlocked(round_id, who, lock.amount));
+            Ok(().into())
+        }
+
+        /// Let `delegate` cast votes of an `ongoing` round paid from the funds of the caller, up
+        /// to `budget` (fees included). Replaces any previous delegation of the round.
+        #[pallet::weight(T::WeightInfo::delegate_votes())]
+        pub fn delegate_votes(
+            origin: OriginFor<T>,
+            round_id: u32,
+            delegate: T::AccountId,
+            budget: u128,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let round = Rounds::<T>::get(round_id).ok_or(Error::<T>::RoundNotExist)?;
+            ensure!(round.ongoing, Error::<T>::RoundHasEnded);
+            VoteDelegations::<T>::insert(
+                round_id,
+                &who,
+                VoteDelegation {
+                    delegate: delegate.clone(),
+                    budget,
+                    spent: 0,
+                },
+            );
+            Self::deposit_event(Event::VotesDelegated(round_id, who, delegate, budget));
+            Ok(().into())
+        }
+
+        /// Remove the delegation of the caller for a round
+        #[pallet::weight(T::WeightInfo::undelegate_votes())]
+        pub fn undelegate_votes(origin: OriginFor<T>, round_id: u32) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                VoteDelegations::<T>::contains_key(round_id, &who),
+                Error::<T>::DelegationNotExist
+            );
+            VoteDelegations::<T>::remove(round_id, &who);
+            Self::deposit_event(Event::VotesUndelegated(round_id, who));
+            Ok(().into())
+        }
+
+        /// Vote to a project on behalf of `delegator`, who pays for the ballots out of the
+        /// budget delegated to the caller
+        #[pallet::weight(T::WeightInfo::vote_as_delegate())]
+        pub fn vote_as_delegate(
+            origin: OriginFor<T>,
+            currency_id: CurrencyIdOf<T>,
+            round_id: u32,
+            hash: T::Hash,
+            ballot: u128,
+            delegator: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let mut delegation = VoteDelegations::<T>::get(round_id, &delegator)
+                .ok_or(Error::<T>::DelegationNotExist)?;
+            ensure!(delegation.delegate == who, Error::<T>::NotDelegate);
+            let round = Rounds::<T>::get(round_id).ok_or(Error::<T>::RoundNotExist)?;
+            let voted = ProjectVotes::<T>::get((round_id, &hash, &delegator)).unwrap_or_default();
+            let amount = Self::cal_amount(round.cost_curve.cost(voted, ballot), false)?;
+            delegation.spent = delegation.spent.saturating_add(amount);
+            ensure!(
+                delegation.spent <= delegation.budget,
+                Error::<T>::DelegationBudgetExceeded
+            );
+            Self::do_vote(
+                delegator.clone(),
+                currency_id,
+                round_id,
+                hash,
+                ballot,
+                Conviction::None,
+            )?;
+            VoteDelegations::<T>::insert(round_id, &delegator, delegation);
+            Self::deposit_event(Event::DelegateVoted(hash, who, delegator, ballot));
+            Ok(().into())
+        }
+
+        /// Submit a vote signed by `signer`, the caller pays the transaction fee and `signer`
+        /// pays for the ballots
+        #[pallet::weight(T::WeightInfo::vote_signed())]
+        pub fn vote_signed(
+            origin: OriginFor<T>,
+            payload: VotePayloadOf<T>,
+            signature: T::OffchainSignature,
+            signer: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let relayer = ensure_signed(origin)?;
+            let message = Self::vote_signing_payload(&payload);
+            // wallets signing raw bytes wrap them in `<Bytes>` tags
+            let wrapped = [&b"<Bytes>"[..], &message[..], &b"</Bytes>"[..]].concat();
+            ensure!(
+                signature.verify(&message[..], &signer) || signature.verify(&wrapped[..], &signer),
+                Error::<T>::InvalidSignature
+            );
+            ensure!(
+                frame_system::Pallet::<T>::block_number() <= payload.expiry,
+                Error::<T>::VotePayloadExpired
+            );
+            ensure!(
+                payload.nonce == VoteNonces::<T>::get(&signer),
+                Error::<T>::InvalidNonce
+            );
+            Self::do_vote(
+                signer.clone(),
+                payload.currency_id,
+                payload.round_id,
+                payload.project,
+                payload.ballot,
+                Conviction::None,
+            )?;
+            VoteNonces::<T>::insert(&signer, payload.nonce.saturating_add(1));
+            Self::deposit_event(Event::SignedVoteRelayed(
+                payload.project,
+                signer,
+                relayer,
+                payload.ballot,
+            ));
+            Ok(().into())
+        }
+
+        /// Select where the fees collected by the pallet are swept to
+        #[pallet::weight(T::WeightInfo::set_fee_destination())]
+        pub fn set_fee_destination(
+            origin: OriginFor<T>,
+            destination: FeeDestination,
+        ) -> DispatchResultWithPostInfo {
+            T::AdminOrigin::ensure_origin(origin)?;
+            CurrentFeeDestination::<T>::put(destination);
+            Self::deposit_event(Event::FeeDestinationSet(destination));
+            Ok(().into())
+        }
+
+        /// Move all the fees collected in `currency_id` to the fee destination, anyone can
+        /// trigger the sweep
+        #[pallet::weight(T::WeightInfo::sweep_fees())]
+        pub fn sweep_fees(
+            origin: OriginFor<T>,
+            currency_id: CurrencyIdOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            let pallet_account = Self::account_id();
+            let fees = T::MultiCurrency::free_balance(currency_id, &pallet_account);
+            ensure!(!fees.is_zero(), Error::<T>::NoFeesToSweep);
+            let destination = CurrentFeeDestination::<T>::get();
+            match destination {
+                FeeDestination::Treasury => {
+                    T::MultiCurrency::transfer(
+                        currency_id,
+                        &pallet_account,
+                        &T::TreasuryAccount::get(),
+                        fees,
+                    )?;
+                }
+                FeeDestination::Burn => {
+                    T::MultiCurrency::withdraw(currency_id, &pallet_account, fees)?;
+                }
+                FeeDestination::Round(round_id) => {
+                    Self::do_donate(
+                        pallet_account,
+                        round_id,
+                        fees,
+                        currency_id,
+                        SponsorConditions::default(),
+                        false,
+                    )?;
+                }
+            }
+            Self::deposit_event(Event::FeesSwept(currency_id, fees, destination));
+            Ok(().into())
+        }
+
+        /// Claim the participation badge of a voter of an ended round, before it is archived
+        #[pallet::weight(T::WeightInfo::claim_voter_badge())]
+        pub fn claim_voter_badge(
+            origin: OriginFor<T>,
+            round_id: u32,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let round = Rounds::<T>::get(round_id).ok_or(Error::<T>::RoundNotExist)?;
+            ensure!(!round.ongoing, Error::<T>::RoundIsOngoing);
+            ensure!(
+                RoundParticipants::<T>::take(round_id, &who).is_some(),
+                Error::<T>::NoBadgeToClaim
+            );
+            let spent = VoterParticipations::<T>::get(round_id, &who)
+                .map(|participation| participation.spent)
+                .unwrap_or_default();
+            let badge = Badge {
+                round_id,
+                kind: BadgeKind::Voter,
+                rank: None,
+                amount: spent,
+            };
+            let item = Self::mint_badge(&who, badge)?;
+            Self::deposit_event(Event::BadgeClaimed(round_id, who, item));
+            Ok(().into())
+        }
+
+        /// Claim the participation badge of a project funded in an ended round, before it is
+        /// archived
+        #[pallet::weight(T::WeightInfo::claim_project_badge())]
+        pub fn claim_project_badge(
+            origin: OriginFor<T>,
+            round_id: u32,
+            hash: T::Hash,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let round = Rounds::<T>::get(round_id).ok_or(Error::<T>::RoundNotExist)?;
+            ensure!(!round.ongoing, Error::<T>::RoundIsOngoing);
+            let project = Projects::<T>::get(round_id, &hash).ok_or(Error::<T>::ProjectNotExist)?;
+            ensure!(project.owner == who, Error::<T>::NotProjectOwner);
+            ensure!(
+                !ProjectBadges::<T>::contains_key(round_id, &hash),
+                Error::<T>::BadgeAlreadyClaimed
+            );
+            let funds = project.grants.saturating_add(project.support_fund);
+            ensure!(!funds.is_zero(), Error::<T>::NoBadgeToClaim);
+            let rank =
+                ProjectRanks::<T>::get(round_id, &hash).ok_or(Error::<T>::RoundNotSettled)?;
+            let badge = Badge {
+                round_id,
+                kind: BadgeKind::Project(hash),
+                rank: Some(rank),
+                amount: funds,
+            };
+            let item = Self::mint_badge(&who, badge)?;
+            ProjectBadges::<T>::insert(round_id, hash, item);
+            Self::deposit_event(Event::BadgeClaimed(round_id, who, item));
+            Ok(().into())
+        }
+
+        /// Remove the lock of the holdings a voter proved once the snapshot of the round passed
+        /// or the round ended, anyone can trigger it
+        #[pallet::weight(T::WeightInfo::unlock_eligibility())]
+        pub fn unlock_eligibility(
+            origin: OriginFor<T>,
+            round_id: u32,
+            who: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            let round = Rounds::<T>::get(round_id).ok_or(Error::<T>::RoundNotExist)?;
+            let currency_id = EligibilityLocks::<T>::get(round_id, &who)
+                .ok_or(Error::<T>::EligibilityLockNotExist)?;
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(
+                !round.ongoing
+                    || round
+                        .eligibility
+                        .and_then(|rule| rule.snapshot)
+                        .map_or(true, |snapshot| now > snapshot),
+                Error::<T>::EligibilityLockNotExpired
+            );
+            T::MultiCurrency::remove_lock(Self::eligibility_lock_id(round_id), currency_id, &who)?;
+            EligibilityLocks::<T>::remove(round_id, &who);
+            Self::deposit_event(Event::EligibilityUnlocked(round_id, who));
+            Ok(().into())
+        }
+    }
+}
+
+/// Badges can not be transferred, to be set as the `Locker` of `pallet_uniques`.
+impl<T: Config> Locker<u32, u32> for Pallet<T> {
+    fn is_locked(collection: u32, _item: u32) -> bool {
+        collection == T::BadgeCollection::get()
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    // Add public immutables and private mutables.
+
+    /// The message the voter signs for a relayed vote: [`VOTE_SIGNING_CONTEXT`], the genesis
+    /// hash of the chain and the payload, SCALE encoded.
+    pub fn vote_signing_payload(payload: &VotePayloadOf<T>) -> Vec<u8> {
+        let genesis_hash = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+        (VOTE_SIGNING_CONTEXT, genesis_hash, payload).encode()
+    }
+
+    pub fn account_id() -> T::AccountId {
+        T::PalletId::get().into_account_truncating()
+    }
+
+    /// get corresponding accounts
     pub fn round_admin_account(round_id: u32) -> T::AccountId {
         let round = Rounds::<T>::get(round_id).unwrap();
         round.admin
     }
 
     pub fn cal_cost(voted: u128, ballot: u128) -> u128 {
-        let mut points = ballot.checked_mul(ballot.checked_add(1).unwrap()).unwrap() / 2;
-        points = points
-            .checked_add(ballot.checked_mul(voted).unwrap())
-            .unwrap();
-        return points;
+        cost::Quadratic.cost(voted, ballot)
     }
 
-    pub fn cal_amount(amount: u128, is_fee: bool) -> u128 {
+    /// What `amount` units of vote cost, or their fee, fails when the cost of the ballots is too
+    /// large to be paid
+    pub fn cal_amount(amount: u128, is_fee: bool) -> Result<u128, DispatchError> {
         let uov = T::UnitOfVote::get();
         let nup = T::NumberOfUnitPerVote::get();
         let frpv = T::FeeRatioPerVote::get();
-        if is_fee {
-            uov.checked_mul(frpv).unwrap().checked_mul(amount).unwrap()
-        } else {
-            uov.checked_mul(nup).unwrap().checked_mul(amount).unwrap()
-        }
+        let unit = if is_fee { frpv } else { nup };
+        uov.checked_mul(unit)
+            .and_then(|price| price.checked_mul(amount))
+            .ok_or_else(|| Error::<T>::InvalidBallot.into())
     }
 
     pub fn u128_to_balance(cost: u128) -> BalanceOf<T> {
@@ -541,28 +2344,946 @@ impl<T: Config> Pallet<T> {
         TryInto::<u128>::try_into(balance).ok().unwrap()
     }
 
+    /// The lock of the conviction voters of a round, each round has its own lock
+    pub fn conviction_lock_id(round_id: u32) -> LockIdentifier {
+        let mut id = *b"qfcv\0\0\0\0";
+        id[4..].copy_from_slice(&round_id.to_le_bytes());
+        id
+    }
+
+    /// The lock of the holdings proved by the voters of a round, each round has its own lock
+    pub fn eligibility_lock_id(round_id: u32) -> LockIdentifier {
+        let mut id = *b"qfel\0\0\0\0";
+        id[4..].copy_from_slice(&round_id.to_le_bytes());
+        id
+    }
+
+    /// Start an `ongoing` round with the settings of a template
+    pub fn do_start_round(round_id: u32, template: RoundTemplateOf<T>) -> DispatchResult {
+        ensure!(
+            !Rounds::<T>::contains_key(&round_id) && !ArchivedRounds::<T>::contains_key(&round_id),
+            Error::<T>::RoundExisted
+        );
+        // the bounds may have changed since the template was stored
+        Self::ensure_valid_template(&template)?;
+        let round = Round {
+            ongoing: true,
+            name: template.name,
+            currency_id: template.currency_id,
+            support_pool: 0,
+            pre_tax_support_pool: 0,
+            total_support_area: 0,
+            total_tax: 0,
+            round_reserve: template.round_reserve,
+            admin: template.admin,
+            matching: template.matching,
+            max_match_per_project: template.max_match_per_project,
+            sponsors: 0,
+            undistributed_pool: 0,
+            eligibility: template.eligibility,
+            limits: template.limits,
+            cost_curve: template.cost_curve,
+        };
+        let admin = round.admin.clone();
+        Rounds::<T>::insert(round_id, round);
+        if !template.challenge_period.is_zero() {
+            ChallengePeriods::<T>::insert(round_id, template.challenge_period);
+        }
+        if !template.conviction_period.is_zero() {
+            ConvictionPeriods::<T>::insert(round_id, template.conviction_period);
+        }
+        T::OnRoundStarted::on_round_started(round_id, &admin);
+        Self::deposit_event(Event::RoundStarted(round_id));
+        Ok(())
+    }
+
+    /// Checks the settings of a template like the setters of a round check them one by one
+    fn ensure_valid_template(template: &RoundTemplateOf<T>) -> DispatchResult {
+        ensure!(
+            template.name.len() >= T::NameMinLength::get().try_into().unwrap(),
+            Error::<T>::RoundNameTooShort
+        );
+        Self::ensure_valid_matching(&template.matching)?;
+        Self::ensure_valid_cost_curve(&template.cost_curve)?;
+        ensure!(
+            Self::matching_fits_cost_curve(&template.matching, &template.cost_curve),
+            Error::<T>::InvalidCostCurve
+        );
+        Self::ensure_valid_challenge_period(template.challenge_period)
+    }
+
+    /// Pairwise matching without a coordination bound matches nothing
+    fn ensure_valid_matching(mode: &MatchingMode) -> DispatchResult {
+        ensure!(
+            *mode != MatchingMode::PairwiseBounded(0),
+            Error::<T>::InvalidMatchingMode
+        );
+        Ok(())
+    }
+
+    /// A capped curve must let ballots cost something
+    fn ensure_valid_cost_curve(curve: &CostCurve) -> DispatchResult {
+        ensure!(
+            *curve != CostCurve::CappedQuadratic(0),
+            Error::<T>::InvalidCostCurve
+        );
+        Ok(())
+    }
+
+    /// The support area sums the products of the ballots, which only follow what the voters paid
+    /// when the n-th ballot costs n
+    fn matching_fits_cost_curve(mode: &MatchingMode, curve: &CostCurve) -> bool {
+        *mode != MatchingMode::SupportArea || *curve == CostCurve::Quadratic
+    }
+
+    /// Projects can only be flagged until the round can be archived
+    fn ensure_valid_challenge_period(period: T::BlockNumber) -> DispatchResult {
+        ensure!(
+            period <= T::ArchiveDelay::get(),
+            Error::<T>::InvalidChallengePeriod
+        );
+        Ok(())
+    }
+
+    pub fn do_register_project(
+        who: T::AccountId,
+        round_id: u32,
+        hash: T::Hash,
+        name: Vec<u8>,
+    ) -> DispatchResult {
+        ensure!(
+            name.len() >= T::NameMinLength::get().try_into().unwrap(),
+            Error::<T>::ProjectNameTooShort
+        );
+        ensure!(
+            name.len() <= T::NameMaxLength::get().try_into().unwrap(),
+            Error::<T>::ProjectNameTooLong
+        );
+        ensure!(
+            Rounds::<T>::contains_key(&round_id),
+            Error::<T>::RoundNotExist
+        );
+        let round = Rounds::<T>::get(round_id).unwrap();
+        ensure!(true == round.ongoing, Error::<T>::RoundHasEnded);
+        ensure!(
+            !Projects::<T>::contains_key(&round_id, &hash),
+            Error::<T>::DuplicateProject
+        );
+        ensure!(
+            RoundSizes::<T>::get(round_id).projects < T::MaxProjectsPerRound::get(),
+            Error::<T>::TooManyProjects
+        );
+
+        let bounded_name: BoundedVec<u8, T::NameMaxLength> = name
+            .clone()
+            .try_into()
+            .map_err(|_| Error::<T>::BadMetadata)?;
+        let project = Project {
+            total_votes: 0,
+            grants: 0,
+            support_area: 0,
+            support_fund: 0,
+            name: bounded_name,
+            owner: who.clone(),
+        };
+        Projects::<T>::insert(round_id, hash, project);
+        RoundSizes::<T>::mutate(round_id, |size| size.projects.saturating_inc());
+        Self::deposit_event(Event::ProjectRegistered(hash, who));
+        Ok(())
+    }
+
+    /// The settings of a round, to start another round like it
+    pub fn template_of_round(round_id: u32) -> Option<RoundTemplateOf<T>> {
+        let round = Rounds::<T>::get(round_id)?;
+        Some(RoundTemplate {
+            name: round.name,
+            currency_id: round.currency_id,
+            admin: round.admin,
+            round_reserve: round.round_reserve,
+            matching: round.matching,
+            max_match_per_project: round.max_match_per_project,
+            eligibility: round.eligibility,
+            limits: round.limits,
+            cost_curve: round.cost_curve,
+            challenge_period: ChallengePeriods::<T>::get(round_id),
+            conviction_period: ConvictionPeriods::<T>::get(round_id),
+        })
+    }
+
+    /// Whether `who` holds as much as required by an eligibility rule
+    pub fn holds_enough(rule: &EligibilityRuleOf<T>, who: &T::AccountId) -> bool {
+        Self::balance_to_u128(T::MultiCurrency::total_balance(rule.currency_id, who))
+            >= rule.min_balance
+    }
+
+    /// The account holding the support pool of a round until it ends
+    pub fn round_account(round_id: u32) -> T::AccountId {
+        T::PalletId::get().into_sub_account_truncating(round_id)
+    }
+
+    /// The value of one unit of `currency_id` in the currency of a round
+    pub fn currency_rate(
+        round_id: u32,
+        round: &RoundOf<T>,
+        currency_id: CurrencyIdOf<T>,
+    ) -> Result<FixedU128, DispatchError> {
+        if currency_id == round.currency_id {
+            return Ok(FixedU128::one());
+        }
+        match RoundCurrencies::<T>::get(round_id, &currency_id) {
+            Some(CurrencyRate::Fixed(rate)) => Ok(rate),
+            Some(CurrencyRate::Oracle) => {
+                price::oracle_rate::<_, T::PriceFeed>(&currency_id, &round.currency_id)
+                    .filter(|rate| !rate.is_zero())
+                    .ok_or_else(|| Error::<T>::PriceNotAvailable.into())
+            }
+            None => Err(Error::<T>::MismatchingCurencyId.into()),
+        }
+    }
+
+    /// Record `amount` of `currency_id` worth `value` paid to the round account
+    pub(crate) fn hold_in_round(
+        round_id: u32,
+        currency_id: CurrencyIdOf<T>,
+        amount: u128,
+        value: u128,
+    ) {
+        RoundHoldings::<T>::mutate(round_id, currency_id, |held| {
+            *held = held.saturating_add(amount)
+        });
+        RoundHeldValue::<T>::mutate(round_id, |held| *held = held.saturating_add(value));
+    }
+
+    /// Pay `value` from the round account, taking the same share of every currency it holds
+    fn pay_from_round(
+        round_id: u32,
+        round: &RoundOf<T>,
+        to: &T::AccountId,
+        value: u128,
+    ) -> DispatchResult {
+        let held_value = RoundHeldValue::<T>::get(round_id);
+        // rounds funded before they could accept other currencies only hold their own
+        if held_value.is_zero() {
+            return T::MultiCurrency::transfer(
+                round.currency_id,
+                &Self::round_account(round_id),
+                to,
+                Self::u128_to_balance(value),
+            );
+        }
+        let value = value.min(held_value);
+        let holdings: Vec<_> = RoundHoldings::<T>::iter_prefix(round_id).collect();
+        for (currency_id, held) in holdings {
+            let amount = multiply_by_rational(held, value, held_value).unwrap_or_default();
+            if amount > 0 {
+                T::MultiCurrency::transfer(
+                    currency_id,
+                    &Self::round_account(round_id),
+                    to,
+                    Self::u128_to_balance(amount),
+                )?;
+                RoundHoldings::<T>::insert(round_id, currency_id, held - amount);
+            }
+        }
+        RoundHeldValue::<T>::insert(round_id, held_value - value);
+        Ok(())
+    }
+
+    /// Vote to a project of an `ongoing` round, the ballots count `conviction` times towards
+    /// the matching of the project
+    pub fn do_vote(
+        who: T::AccountId,
+        currency_id: CurrencyIdOf<T>,
+        round_id: u32,
+        hash: T::Hash,
+        ballot: u128,
+        conviction: Conviction,
+    ) -> DispatchResult {
+        // check whether this round still ongoing
+        ensure!(
+            Rounds::<T>::contains_key(&round_id),
+            Error::<T>::RoundNotExist
+        );
+        let round = Rounds::<T>::get(round_id).unwrap();
+        ensure!(true == round.ongoing, Error::<T>::RoundHasEnded);
+        ensure!(
+            Projects::<T>::contains_key(&round_id, &hash),
+            Error::<T>::ProjectNotExist
+        );
+        let rate = Self::currency_rate(round_id, &round, currency_id)?;
+        ensure!(ballot > 0, Error::<T>::InvalidBallot);
+        if let Some(rule) = &round.eligibility {
+            let eligible = match rule.snapshot {
+                Some(_) => EligibleVoters::<T>::contains_key(round_id, &who),
+                None => Self::holds_enough(rule, &who),
+            };
+            ensure!(eligible, Error::<T>::NotEligible);
+        }
+        ensure!(
+            conviction == Conviction::None || !ConvictionPeriods::<T>::get(round_id).is_zero(),
+            Error::<T>::ConvictionNotEnabled
+        );
+        // conviction locks are kept in the currency of the round
+        ensure!(
+            conviction == Conviction::None || currency_id == round.currency_id,
+            Error::<T>::MismatchingCurencyId
+        );
+
+        let voted = match ProjectVotes::<T>::get((round_id, &hash, &who)) {
+            Some(val) => val,
+            None => {
+                let voters = ProjectVoters::<T>::get(round_id, &hash);
+                // only the pairwise matching goes over the pairs of voters of a project
+                if let MatchingMode::PairwiseBounded(_) = round.matching {
+                    ensure!(
+                        voters < T::MaxVotersPerProject::get(),
+                        Error::<T>::TooManyVoters
+                    );
+                }
+                ensure!(
+                    RoundSizes::<T>::get(round_id).votes < T::MaxVotesPerRound::get(),
+                    Error::<T>::TooManyVotes
+                );
+                ProjectVoters::<T>::insert(round_id, &hash, voters + 1);
+                RoundSizes::<T>::mutate(round_id, |size| {
+                    size.voters_per_project = size.voters_per_project.max(voters + 1);
+                    size.votes.saturating_inc();
+                });
+                0
+            }
+        };
+
+        // check whether staked, every voter is recorded to claim a badge once the round is ended
+        let staked = RoundParticipants::<T>::get(round_id, &who);
+        if staked == None {
+            if round.round_reserve > 0 {
+                let reserve_num = T::ReserveUnit::get()
+                    .checked_mul(round.round_reserve)
+                    .unwrap();
+                let reserve_balance = TryInto::<DoraBalance<T>>::try_into(reserve_num)
+                    .ok()
+                    .unwrap();
+                T::Currency::reserve_named(&RESERVE_ID, &who, reserve_balance)
+                    .map_err(|_| Error::<T>::InsufficientReserveDora)?;
+                RoundReserves::<T>::insert(round_id, &who, reserve_num);
+            }
+            RoundParticipants::<T>::insert(round_id, &who, round.round_reserve > 0);
+            RoundSizes::<T>::mutate(round_id, |size| size.participants.saturating_inc());
+        }
+        let cost = round.cost_curve.cost(voted, ballot);
+        let amount = Self::cal_amount(cost, false)?;
+        let fee = Self::cal_amount(cost, true)?;
+        if let Some(max_ballots) = round.limits.max_ballots_per_project {
+            ensure!(
+                voted.saturating_add(ballot) <= max_ballots,
+                Error::<T>::TooManyBallots
+            );
+        }
+        if let Some(max_spend) = round.limits.max_spend {
+            let spent = VoterParticipations::<T>::get(round_id, &who)
+                .map(|participation| participation.spent)
+                .unwrap_or_default();
+            ensure!(
+                spent.saturating_add(amount) <= max_spend,
+                Error::<T>::SpendingCapExceeded
+            );
+        }
+
+        // what is paid in another currency is converted at the rate of the round
+        let fee_paid = price::from_base(rate, fee).ok_or(Error::<T>::PriceNotAvailable)?;
+        let grants_paid =
+            price::from_base(rate, amount - fee).ok_or(Error::<T>::PriceNotAvailable)?;
+        let _ = T::MultiCurrency::transfer(
+            currency_id,
+            &who,
+            &Self::account_id(),
+            Self::u128_to_balance(fee_paid),
+        )?;
+        // votes for projects with milestones or vested grants are kept by the round account, so
+        // are the grants paid in another currency, the admin gets them in the same share of every
+        // currency as the matching when the round is settled
+        let escrowed = Milestones::<T>::contains_key(round_id, &hash)
+            || RoundVesting::<T>::contains_key(round_id);
+        let held = escrowed || currency_id != round.currency_id;
+        let grants_account = if held {
+            Self::round_account(round_id)
+        } else {
+            Self::round_admin_account(round_id)
+        };
+        // transfer first, update last, as transfer will ensure the free balance is enough
+        let _ = T::MultiCurrency::transfer(
+            currency_id,
+            &who,
+            &grants_account,
+            Self::u128_to_balance(grants_paid),
+        )?;
+        if held {
+            Self::hold_in_round(round_id, currency_id, grants_paid, amount - fee);
+        }
+        if held && !escrowed {
+            HeldGrants::<T>::mutate(round_id, &hash, |grants| {
+                *grants = grants.saturating_add(amount - fee)
+            });
+        }
+        // conviction voters keep as much as they paid locked
+        if conviction != Conviction::None {
+            let mut lock = ConvictionLocks::<T>::get(round_id, &who).unwrap_or_default();
+            lock.amount = lock.amount.saturating_add(amount);
+            lock.conviction = lock.conviction.max(conviction);
+            ensure!(
+                Self::balance_to_u128(T::MultiCurrency::free_balance(currency_id, &who))
+                    >= lock.amount,
+                Error::<T>::InsufficientBalanceToLock
+            );
+            T::MultiCurrency::set_lock(
+                Self::conviction_lock_id(round_id),
+                currency_id,
+                &who,
+                Self::u128_to_balance(lock.amount),
+            )?;
+            ConvictionLocks::<T>::insert(round_id, &who, lock);
+            BoostedVotes::<T>::mutate((round_id, &hash, &who), |boost| {
+                *boost = boost.saturating_add(ballot.saturating_mul(conviction.multiplier() - 1))
+            });
+        }
+        VoterParticipations::<T>::try_mutate(round_id, &who, |participation| -> DispatchResult {
+            let participation = participation.get_or_insert_with(Default::default);
+            if !participation.projects.contains(&hash) {
+                participation
+                    .projects
+                    .try_push(hash)
+                    .map_err(|_| Error::<T>::TooManyVotedProjects)?;
+            }
+            participation.spent = participation.spent.saturating_add(amount);
+            Ok(())
+        })?;
+        // update the project and corresponding round
+        ProjectVotes::<T>::insert((round_id, &hash, &who), ballot + voted);
+        Projects::<T>::mutate(round_id, hash, |poj| {
+            match poj {
+                Some(project) => {
+                    let support_area = ballot
+                        .checked_mul(conviction.multiplier())
+                        .unwrap()
+                        .checked_mul(project.total_votes - voted)
+                        .unwrap();
+                    project.support_area = support_area.checked_add(project.support_area).unwrap();
+                    project.total_votes += ballot;
+                    project.grants += amount - fee;
+                    //debug::info!("Total votes: {:?}, Current votes: {:?}, Support Area: {:?},Est cost: {:?}",
+                    // poj.total_votes, voted, support_area, cost);
+                    Rounds::<T>::mutate(round_id, |rnd| match rnd {
+                        Some(round) => {
+                            let tsa = round.total_support_area;
+                            let tt = round.total_tax;
+                            round.total_support_area = support_area.checked_add(tsa).unwrap();
+                            round.total_tax = fee.checked_add(tt).unwrap();
+                        }
+                        _ => (),
+                    });
+                }
+                _ => (),
+            }
+        });
+        T::OnVote::on_vote(round_id, &hash, &who, ballot, amount);
+        Self::deposit_event(Event::VoteSucceed(hash, who, ballot));
+        Ok(())
+    }
+
+    pub fn do_donate(
+        who: T::AccountId,
+        round_id: u32,
+        amount: BalanceOf<T>,
+        currency_id: CurrencyIdOf<T>,
+        conditions: SponsorConditions<T::BlockNumber>,
+        charge_fee: bool,
+    ) -> DispatchResult {
+        ensure!(
+            Rounds::<T>::contains_key(&round_id),
+            Error::<T>::RoundNotExist
+        );
+        let round = Rounds::<T>::get(round_id).unwrap();
+        ensure!(true == round.ongoing, Error::<T>::RoundHasEnded);
+        let rate = Self::currency_rate(round_id, &round, currency_id)?;
+        // the minimum unit, make sure the donate is greater than this
+        let min_unit_number = Self::cal_amount(1u128, false)?;
+        let paid_number = Self::balance_to_u128(amount);
+        let paid_fee = if charge_fee {
+            T::FeeRatioPerVote::get()
+                .checked_mul(paid_number / T::NumberOfUnitPerVote::get())
+                .unwrap()
+        } else {
+            0
+        };
+        // the round accounts for donations in its own currency
+        let fee_number = price::to_base(rate, paid_fee);
+        let amount_number = fee_number.saturating_add(price::to_base(rate, paid_number - paid_fee));
+        ensure!(
+            amount_number > min_unit_number,
+            Error::<T>::DonationTooSmall
+        );
+        let mut sponsorship = match Sponsorships::<T>::get(round_id, &who) {
+            Some(sponsorship) => {
+                ensure!(
+                    sponsorship.conditions == conditions,
+                    Error::<T>::ConflictingSponsorConditions
+                );
+                sponsorship
+            }
+            None => {
+                // the treasury always has a slot, the others pay a minimum to take one
+                if who != T::TreasuryAccount::get() {
+                    ensure!(
+                        amount_number >= T::MinSponsorship::get(),
+                        Error::<T>::SponsorshipTooSmall
+                    );
+                    ensure!(
+                        round.sponsors < T::MaxSponsors::get(),
+                        Error::<T>::TooManySponsors
+                    );
+                }
+                Sponsorship {
+                    amount: 0,
+                    conditions,
+                    void: false,
+                    refunded: false,
+                }
+            }
+        };
+        let is_new_sponsor = sponsorship.amount == 0;
+
+        if paid_fee > 0 {
+            let _ = T::MultiCurrency::transfer(
+                currency_id,
+                &who,
+                &Self::account_id(),
+                Self::u128_to_balance(paid_fee),
+            )?;
+        }
+        // the pool is kept by the round account until the round ends
+        let _ = T::MultiCurrency::transfer(
+            currency_id,
+            &who,
+            &Self::round_account(round_id),
+            Self::u128_to_balance(paid_number - paid_fee),
+        )?;
+        Self::hold_in_round(
+            round_id,
+            currency_id,
+            paid_number - paid_fee,
+            amount_number - fee_number,
+        );
+        sponsorship.amount = sponsorship
+            .amount
+            .checked_add(amount_number - fee_number)
+            .unwrap();
+        Sponsorships::<T>::insert(round_id, &who, sponsorship);
+        // update the round
+        Rounds::<T>::mutate(round_id, |rnd| match rnd {
+            Some(round) => {
+                let ptsp = round.pre_tax_support_pool;
+                let sp = round.support_pool;
+                let tt = round.total_tax;
+                round.pre_tax_support_pool = amount_number.checked_add(ptsp).unwrap();
+                round.support_pool = (amount_number - fee_number).checked_add(sp).unwrap();
+                round.total_tax = fee_number.checked_add(tt).unwrap();
+                if is_new_sponsor {
+                    round.sponsors += 1;
+                }
+            }
+            _ => (),
+        });
+        Self::deposit_event(Event::DonateSucceed(round_id, who, amount));
+        Ok(())
+    }
+
+    /// Pay the matching of the projects of an ended round. Projects with milestones or vested
+    /// grants are paid from the round account, the round admin pays the others with their
+    /// matching and the grants held for them.
+    fn settle_round(round_id: u32, round: &RoundOf<T>) -> DispatchResult {
+        let deadline =
+            frame_system::Pallet::<T>::block_number().saturating_add(T::MilestoneDeadline::get());
+        let vested = RoundVesting::<T>::contains_key(round_id);
+        let mut to_admin = 0u128;
+        for (hash, project) in Projects::<T>::iter_prefix(round_id) {
+            let total = project.grants.saturating_add(project.support_fund);
+            if Milestones::<T>::contains_key(round_id, hash) {
+                Escrows::<T>::insert(
+                    round_id,
+                    hash,
+                    Escrow {
+                        total,
+                        released: 0,
+                        deadline,
+                    },
+                );
+            } else if vested {
+                VestedGrants::<T>::insert(
+                    round_id,
+                    hash,
+                    VestedGrant {
+                        total,
+                        claimed: 0,
+                        revoked: false,
+                    },
+                );
+            } else {
+                to_admin = to_admin
+                    .saturating_add(project.support_fund)
+                    .saturating_add(HeldGrants::<T>::get(round_id, hash));
+            }
+        }
+        if to_admin > 0 {
+            Self::pay_from_round(round_id, round, &round.admin, to_admin)?;
+        }
+        Self::rank_projects(round_id);
+        Ok(())
+    }
+
+    /// Rank the projects of a round by their funds once they are final, projects with the same
+    /// funds share their rank. Returns the number of projects ranked
+    pub(crate) fn rank_projects(round_id: u32) -> u32 {
+        let mut ranking: Vec<(T::Hash, u128)> = Projects::<T>::iter_prefix(round_id)
+            .map(|(hash, project)| (hash, project.grants.saturating_add(project.support_fund)))
+            .collect();
+        ranking.sort_by(|a, b| b.1.cmp(&a.1));
+        let ranked = ranking.len() as u32;
+        let mut rank = 0u32;
+        let mut previous = None;
+        for (index, (hash, funds)) in ranking.into_iter().enumerate() {
+            if previous != Some(funds) {
+                rank = index as u32 + 1;
+                previous = Some(funds);
+            }
+            ProjectRanks::<T>::insert(round_id, hash, rank);
+        }
+        ranked
+    }
+
+    /// Share the matching of a flagged project between the other projects of its round which
+    /// are not flagged, in proportion to their matching. Without such projects the matching is
+    /// refunded to the sponsors.
+    fn redistribute_matching(round_id: u32, hash: T::Hash) -> DispatchResult {
+        let mut round = Rounds::<T>::get(round_id).ok_or(Error::<T>::RoundNotExist)?;
+        let mut project = Projects::<T>::get(round_id, &hash).ok_or(Error::<T>::ProjectNotExist)?;
+        let frozen = project.support_fund;
+        project.support_fund = 0;
+        Projects::<T>::insert(round_id, hash, project);
+
+        let (hashes, weights): (Vec<T::Hash>, Vec<u128>) = Projects::<T>::iter_prefix(round_id)
+            .filter(|(other, _)| !Flags::<T>::contains_key(round_id, other))
+            .map(|(other, project)| (other, project.support_fund))
+            .unzip();
+        let mut shared = 0u128;
+        for (other, share) in hashes
+            .into_iter()
+            .zip(matching::distribute(frozen, &weights))
+        {
+            shared = shared.saturating_add(share);
+            Projects::<T>::mutate(round_id, other, |poj| {
+                if let Some(project) = poj {
+                    project.support_fund = project.support_fund.saturating_add(share);
+                }
+            });
+        }
+        round.undistributed_pool = round
+            .undistributed_pool
+            .saturating_add(frozen.saturating_sub(shared));
+        Rounds::<T>::insert(round_id, round);
+        Ok(())
+    }
+
+    /// The part of a vested grant unlocked so far by the vesting schedule of its round
+    pub fn vested_amount(round_id: u32, grant: &VestedGrant) -> u128 {
+        // a revoked grant only holds what was vested
+        if grant.revoked {
+            return grant.total;
+        }
+        let schedule = match RoundVesting::<T>::get(round_id) {
+            Some(schedule) => schedule,
+            None => return grant.total,
+        };
+        let elapsed = frame_system::Pallet::<T>::block_number().saturating_sub(schedule.start);
+        if elapsed < schedule.cliff {
+            return 0;
+        }
+        primitives::vesting::linear_release(
+            grant.total,
+            elapsed.saturated_into::<u128>(),
+            schedule.duration.saturated_into::<u128>(),
+        )
+    }
+
+    /// Whether the conditions of a sponsor still hold for a round with `projects` projects
+    pub fn sponsor_conditions_met(
+        conditions: &SponsorConditions<T::BlockNumber>,
+        projects: u32,
+        now: T::BlockNumber,
+    ) -> bool {
+        projects >= conditions.min_projects
+            && conditions.expiry.map_or(true, |expiry| now <= expiry)
+    }
+
+    /// What a sponsor gets back from the round account: its whole donation when void, otherwise
+    /// its share of what was not distributed
+    pub fn sponsor_refund(round: &RoundOf<T>, sponsorship: &Sponsorship<T::BlockNumber>) -> u128 {
+        if sponsorship.void {
+            sponsorship.amount
+        } else {
+            multiply_by_rational(
+                sponsorship.amount,
+                round.undistributed_pool,
+                round.support_pool,
+            )
+            .unwrap_or_default()
+        }
+    }
+
+    /// Whether nothing is left to pay to the projects, sponsors and conviction voters of an
+    /// ended round, nor to unlock for the voters who proved their holdings
+    pub fn payouts_done(round_id: u32, round: &RoundOf<T>) -> bool {
+        Escrows::<T>::iter_prefix_values(round_id).all(|escrow| escrow.released >= escrow.total)
+            && VestedGrants::<T>::iter_prefix_values(round_id)
+                .all(|grant| grant.claimed >= grant.total)
+            && Sponsorships::<T>::iter_prefix_values(round_id).all(|sponsorship| {
+                sponsorship.refunded || Self::sponsor_refund(round, &sponsorship).is_zero()
+            })
+            && ConvictionLocks::<T>::iter_key_prefix(round_id)
+                .next()
+                .is_none()
+            && EligibilityLocks::<T>::iter_key_prefix(round_id)
+                .next()
+                .is_none()
+    }
+
+    /// Move the projects of an archived round to their summaries and remove the rest of its
+    /// storage, at most `limit` entries at a time. Returns the number of removed entries and
+    /// whether the round is fully pruned.
+    pub fn prune_round(round_id: u32, limit: u32) -> (u32, bool) {
+        let mut removed = 0u32;
+        for (hash, project) in Projects::<T>::drain_prefix(round_id).take(limit as usize) {
+            let summary = ProjectSummary {
+                name: project.name,
+                owner: project.owner,
+                total_votes: project.total_votes,
+                grants: project.grants,
+                support_fund: project.support_fund,
+            };
+            ArchivedProjects::<T>::insert(round_id, hash, summary);
+            removed += 1;
+        }
+        let clears: [fn(u32, u32) -> MultiRemovalResults; 18] = [
+            |round_id, limit| ProjectVotes::<T>::clear_prefix((round_id,), limit, None),
+            |round_id, limit| ProjectVoters::<T>::clear_prefix(round_id, limit, None),
+            |round_id, limit| BoostedVotes::<T>::clear_prefix((round_id,), limit, None),
+            |round_id, limit| RoundParticipants::<T>::clear_prefix(round_id, limit, None),
+            |round_id, limit| Sponsorships::<T>::clear_prefix(round_id, limit, None),
+            |round_id, limit| Milestones::<T>::clear_prefix(round_id, limit, None),
+            |round_id, limit| Escrows::<T>::clear_prefix(round_id, limit, None),
+            |round_id, limit| VestedGrants::<T>::clear_prefix(round_id, limit, None),
+            |round_id, limit| Flags::<T>::clear_prefix(round_id, limit, None),
+            |round_id, limit| ConvictionLocks::<T>::clear_prefix(round_id, limit, None),
+            |round_id, limit| VoteDelegations::<T>::clear_prefix(round_id, limit, None),
+            |round_id, limit| EligibleVoters::<T>::clear_prefix(round_id, limit, None),
+            |round_id, limit| EligibilityLocks::<T>::clear_prefix(round_id, limit, None),
+            |round_id, limit| VoterParticipations::<T>::clear_prefix(round_id, limit, None),
+            |round_id, limit| RoundCurrencies::<T>::clear_prefix(round_id, limit, None),
+            |round_id, limit| RoundHoldings::<T>::clear_prefix(round_id, limit, None),
+            |round_id, limit| HeldGrants::<T>::clear_prefix(round_id, limit, None),
+            |round_id, limit| ProjectRanks::<T>::clear_prefix(round_id, limit, None),
+        ];
+        for clear in clears {
+            if removed >= limit {
+                return (removed, false);
+            }
+            let result = clear(round_id, limit - removed);
+            removed = removed.saturating_add(result.unique);
+            if result.maybe_cursor.is_some() {
+                return (removed, false);
+            }
+        }
+        ChallengePeriods::<T>::remove(round_id);
+        ConvictionPeriods::<T>::remove(round_id);
+        RoundVesting::<T>::remove(round_id);
+        RoundEndedAt::<T>::remove(round_id);
+        RoundHeldValue::<T>::remove(round_id);
+        RoundSizes::<T>::remove(round_id);
+        RoundsToPrune::<T>::remove(round_id);
+        Self::deposit_event(Event::RoundPruned(round_id));
+        (removed, true)
+    }
+
+    /// The projects `who` voted for in a round with its ballots, and how much it paid
+    pub fn participation_in_round(
+        round_id: u32,
+        who: T::AccountId,
+    ) -> Option<(Vec<(T::Hash, u128)>, u128)> {
+        let participation = VoterParticipations::<T>::get(round_id, &who)?;
+        let projects = participation
+            .projects
+            .into_iter()
+            .map(|hash| {
+                let ballots = ProjectVotes::<T>::get((round_id, &hash, &who)).unwrap_or_default();
+                (hash, ballots)
+            })
+            .collect();
+        Some((projects, participation.spent))
+    }
+
+    /// The participation of `who` in the rounds it voted in among the `count` round ids from
+    /// `from_round`, at most [`MAX_HISTORY_ROUNDS`] are looked up. Pruned rounds are not included
+    pub fn voter_history(
+        who: T::AccountId,
+        from_round: u32,
+        count: u32,
+    ) -> Vec<(u32, Vec<(T::Hash, u128)>, u128)> {
+        let to_round = from_round.saturating_add(count.min(MAX_HISTORY_ROUNDS));
+        (from_round..to_round)
+            .filter_map(|round_id| {
+                Self::participation_in_round(round_id, who.clone())
+                    .map(|(projects, spent)| (round_id, projects, spent))
+            })
+            .collect()
+    }
+
     // TODO: There is a bug for serde_json, can not use u128 https://github.com/paritytech/substrate/issues/4641
     pub fn vote_cost(who: T::AccountId, round_id: u32, hash: T::Hash, ballot: u32) -> u32 {
-        // need to calculate hash of project hash and round_id combination here to avoid conflicts of projects in different rounds
-        let vote_hash = T::Hashing::hash_of(&(&hash, &round_id));
-        let voted = ProjectVotes::<T>::get(vote_hash, &who).unwrap();
-        TryInto::<u32>::try_into(Self::cal_cost(voted, ballot.into()))
+        let voted = ProjectVotes::<T>::get((round_id, &hash, &who)).unwrap();
+        let curve = Rounds::<T>::get(round_id)
+            .map(|round| round.cost_curve)
+            .unwrap_or_default();
+        TryInto::<u32>::try_into(curve.cost(voted, ballot.into()))
             .ok()
             .unwrap()
     }
 
+    /// The contributions of every voter of a project, as seen by the matching formulas
+    pub fn project_contributions(round_id: u32, hash: T::Hash) -> Vec<Contribution<T::AccountId>> {
+        let curve = Rounds::<T>::get(round_id)
+            .map(|round| round.cost_curve)
+            .unwrap_or_default();
+        ProjectVotes::<T>::iter_prefix((round_id, hash))
+            .map(|(who, ballots)| {
+                // conviction boosts the ballots and what they are worth alike
+                let boosted =
+                    ballots.saturating_add(BoostedVotes::<T>::get((round_id, &hash, &who)));
+                // the ballots were paid for, so their cost fits
+                let amount = Self::cal_amount(curve.cost(0, ballots), false).unwrap_or(u128::MAX);
+                Contribution {
+                    who,
+                    ballots: boosted,
+                    amount: multiply_by_rational(amount, boosted, ballots).unwrap_or(amount),
+                }
+            })
+            .collect()
+    }
+
+    /// Share the support pool of a round between its projects with the round's matching formula,
+    /// no project receives more than the round's `max_match_per_project`
+    pub fn support_funds(round_id: u32, round: &RoundOf<T>) -> Vec<(T::Hash, u128)> {
+        let (hashes, contributions): (Vec<T::Hash>, Vec<Vec<Contribution<T::AccountId>>>) =
+            Projects::<T>::iter_key_prefix(round_id)
+                .map(|hash| (hash, Self::project_contributions(round_id, hash)))
+                .unzip();
+        let weights = round.matching.weights(&contributions);
+        hashes
+            .into_iter()
+            .zip(matching::distribute_capped(
+                round.support_pool,
+                &weights,
+                round.max_match_per_project,
+            ))
+            .collect()
+    }
+
+    /// The witness of the current size of a round to end it with
+    pub fn round_witness(round_id: u32) -> RoundWitness {
+        let size = RoundSizes::<T>::get(round_id);
+        RoundWitness {
+            projects: size.projects,
+            votes: size.votes,
+            voters_per_project: size.voters_per_project,
+            participants: size.participants,
+            sponsors: Sponsorships::<T>::iter_key_prefix(round_id).count() as u32,
+        }
+    }
+
+    /// The weight of ending a round of the size given by `witness`. The pairwise matching goes
+    /// over the pairs of voters of each project, at most `votes * (voters_per_project - 1) / 2`.
+    pub fn end_round_weight(witness: &RoundWitness, pairwise: bool) -> Weight {
+        let weight = T::WeightInfo::end_round(
+            witness.projects,
+            witness.votes,
+            witness.participants,
+            witness.sponsors,
+        );
+        if !pairwise {
+            return weight;
+        }
+        // the rounds using the pairwise matching never have more voters per project than this
+        let voters = witness
+            .voters_per_project
+            .min(T::MaxVotersPerProject::get());
+        let pairs = witness.votes.saturating_mul(voters.saturating_sub(1)) / 2;
+        weight.saturating_add(T::WeightInfo::pairwise_matching(pairs))
+    }
+
+    /// What the projects of a round got, the matching is known once the round is ended
+    pub fn project_results(round_id: u32) -> Vec<ProjectResult<T::AccountId, T::Hash>> {
+        Projects::<T>::iter_prefix(round_id)
+            .map(|(hash, project)| ProjectResult {
+                hash,
+                owner: project.owner,
+                total_votes: project.total_votes,
+                grants: project.grants,
+                support_fund: project.support_fund,
+            })
+            .collect()
+    }
+
+    /// Mint the next item of the badge collection to `who`, creating the collection on first use
+    pub fn mint_badge(who: &T::AccountId, badge: Badge<T::Hash>) -> Result<u32, DispatchError> {
+        let collection = T::BadgeCollection::get();
+        if T::Badges::collection_owner(&collection).is_none() {
+            let owner = Self::account_id();
+            T::Badges::create_collection(&collection, &owner, &owner)?;
+        }
+        let item = NextBadgeId::<T>::get();
+        T::Badges::mint_into(&collection, &item, who)?;
+        // the attributes are kept on the item, where wallets and indexers look for them
+        T::Badges::set_typed_attribute(&collection, &item, b"round", &badge.round_id)?;
+        T::Badges::set_typed_attribute(&collection, &item, b"kind", &badge.kind)?;
+        if let Some(rank) = badge.rank {
+            T::Badges::set_typed_attribute(&collection, &item, b"rank", &rank)?;
+        }
+        T::Badges::set_typed_attribute(&collection, &item, b"amount", &badge.amount)?;
+        NextBadgeId::<T>::put(item.saturating_add(1));
+        Ok(item)
+    }
+
+    /// The attributes of a participation badge, read from its item
+    pub fn badge(item: u32) -> Option<Badge<T::Hash>> {
+        let collection = T::BadgeCollection::get();
+        Some(Badge {
+            round_id: T::Badges::typed_attribute(&collection, &item, b"round")?,
+            kind: T::Badges::typed_attribute(&collection, &item, b"kind")?,
+            rank: T::Badges::typed_attribute(&collection, &item, b"rank"),
+            amount: T::Badges::typed_attribute(&collection, &item, b"amount")?,
+        })
+    }
+
     // TODO, using struct is a little complicate, use tuple instead
     // (project_id, total_votes, grants, support_grants)
     pub fn projects_per_round(round_id: u32) -> Vec<(T::Hash, u32, u32, u32)> {
         let mut projects = vec![];
         let round = Rounds::<T>::get(round_id).unwrap();
-        let area = round.total_support_area;
-        let pool = round.support_pool;
+        let support_funds = Self::support_funds(round_id, &round);
         for (hash, project) in Projects::<T>::iter_prefix(round_id) {
-            let mut sg = 0;
-            if area > 0 {
-                sg = project.support_area.checked_mul(pool / area).unwrap()
-            }
+            let sg = support_funds
+                .iter()
+                .find(|(h, _)| *h == hash)
+                .map(|(_, fund)| *fund)
+                .unwrap_or_default();
             let total_votes = TryInto::<u32>::try_into(project.total_votes).ok().unwrap();
             let grants =
                 TryInto::<u32>::try_into(project.grants.checked_div(T::UnitOfVote::get()).unwrap())
@@ -577,3 +3298,73 @@ impl<T: Config> Pallet<T> {
         projects
     }
 }
+
+impl<T: Config> QuadraticFundingInterface<T::AccountId, T::Hash, CurrencyIdOf<T>> for Pallet<T> {
+    type Origin = T::Origin;
+
+    fn create_round(
+        origin: T::Origin,
+        round_id: u32,
+        currency_id: CurrencyIdOf<T>,
+        name: Vec<u8>,
+        admin: T::AccountId,
+        round_reserve: u128,
+    ) -> DispatchResult {
+        Self::start_round(origin, round_id, currency_id, name, admin, round_reserve)
+            .map(|_| ())
+            .map_err(|e| e.error)
+    }
+
+    fn register_project_for(
+        origin: T::Origin,
+        owner: T::AccountId,
+        round_id: u32,
+        hash: T::Hash,
+        name: Vec<u8>,
+    ) -> DispatchResult {
+        if let Err(origin) = T::AdminOrigin::try_origin(origin) {
+            ensure!(ensure_signed(origin)? == owner, BadOrigin);
+        }
+        Self::do_register_project(owner, round_id, hash, name)
+    }
+
+    fn vote_for(
+        origin: T::Origin,
+        voter: T::AccountId,
+        currency_id: CurrencyIdOf<T>,
+        round_id: u32,
+        hash: T::Hash,
+        ballot: u128,
+    ) -> DispatchResult {
+        let who = ensure_signed(origin.clone())?;
+        if who == voter {
+            Self::vote(origin, currency_id, round_id, hash, ballot)
+        } else {
+            Self::vote_as_delegate(origin, currency_id, round_id, hash, ballot, voter)
+        }
+        .map(|_| ())
+        .map_err(|e| e.error)
+    }
+
+    fn round_results(round_id: u32) -> Option<Vec<ProjectResult<T::AccountId, T::Hash>>> {
+        match Rounds::<T>::get(round_id) {
+            Some(round) if round.ongoing => None,
+            Some(_) => Some(Self::project_results(round_id)),
+            None => {
+                ArchivedRounds::<T>::get(round_id)?;
+                // the projects not archived yet are still being pruned
+                let mut results = Self::project_results(round_id);
+                results.extend(ArchivedProjects::<T>::iter_prefix(round_id).map(
+                    |(hash, project)| ProjectResult {
+                        hash,
+                        owner: project.owner,
+                        total_votes: project.total_votes,
+                        grants: project.grants,
+                        support_fund: project.support_fund,
+                    },
+                ));
+                Some(results)
+            }
+        }
+    }
+}
@@ -0,0 +1,202 @@
+//! Matching formulas used to split the support pool of a round between its projects.
+//!
+//! A formula receives the contributions of every project of a round and returns one matching
+//! weight per project, the support pool is then shared in proportion to these weights.
+//! New formulas only need to implement [`MatchingFormula`] and be added to [`MatchingMode`].
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::{
+    helpers_128bit::multiply_by_rational, traits::IntegerSquareRoot, Perbill, RuntimeDebug,
+};
+use sp_std::{collections::btree_map::BTreeMap, vec, vec::Vec};
+
+/// The contribution of one voter to one project.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct Contribution<AccountId> {
+    pub who: AccountId,
+    /// Total number of ballots the voter cast for the project
+    pub ballots: u128,
+    /// Total amount the voter paid for those ballots
+    pub amount: u128,
+}
+
+/// Turn the contributions of each project into matching weights.
+pub trait MatchingFormula<AccountId> {
+    /// `projects[i]` holds the contributions of the i-th project, the returned vec holds the
+    /// matching weight of each project in the same order.
+    fn weights(&self, projects: &[Vec<Contribution<AccountId>>]) -> Vec<u128>;
+}
+
+/// The matching algorithm used by a round.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub enum MatchingMode {
+    /// Sum of the cross products of the ballots, the original dora matching. Only used with
+    /// the quadratic cost curve, the ballots do not follow what was paid under the others.
+    SupportArea,
+    /// Classic CLR: `(sum of sqrt(contribution))^2 - sum of contribution`.
+    Clr,
+    /// Pairwise-bounded QF, every pair of voters is subsidised with
+    /// `sqrt(c_i * c_j) * M / (M + k_ij)` where `k_ij` is how much the pair coordinates over all
+    /// projects of the round and `M` is the given coordination bound. Its cost grows with the
+    /// square of the voters of a project, which `MaxVotersPerProject` bounds.
+    PairwiseBounded(u128),
+}
+
+impl Default for MatchingMode {
+    fn default() -> Self {
+        MatchingMode::SupportArea
+    }
+}
+
+impl<AccountId: Ord> MatchingFormula<AccountId> for MatchingMode {
+    fn weights(&self, projects: &[Vec<Contribution<AccountId>>]) -> Vec<u128> {
+        match self {
+            MatchingMode::SupportArea => SupportArea.weights(projects),
+            MatchingMode::Clr => Clr.weights(projects),
+            MatchingMode::PairwiseBounded(coordination) => PairwiseBounded {
+                coordination: *coordination,
+            }
+            .weights(projects),
+        }
+    }
+}
+
+/// `((sum of ballots)^2 - sum of ballots^2) / 2`, equal to the `support_area` accumulated by
+/// `vote`.
+pub struct SupportArea;
+
+impl<AccountId> MatchingFormula<AccountId> for SupportArea {
+    fn weights(&self, projects: &[Vec<Contribution<AccountId>>]) -> Vec<u128> {
+        projects
+            .iter()
+            .map(|contributions| cross_products(contributions.iter().map(|c| c.ballots)) / 2)
+            .collect()
+    }
+}
+
+/// `(sum of sqrt(amount))^2 - sum of amount`.
+pub struct Clr;
+
+impl<AccountId> MatchingFormula<AccountId> for Clr {
+    fn weights(&self, projects: &[Vec<Contribution<AccountId>>]) -> Vec<u128> {
+        projects
+            .iter()
+            .map(|contributions| {
+                cross_products(contributions.iter().map(|c| c.amount.integer_sqrt()))
+            })
+            .collect()
+    }
+}
+
+/// Pairwise coordination subsidy, see [`MatchingMode::PairwiseBounded`].
+pub struct PairwiseBounded {
+    pub coordination: u128,
+}
+
+impl<AccountId: Ord> MatchingFormula<AccountId> for PairwiseBounded {
+    fn weights(&self, projects: &[Vec<Contribution<AccountId>>]) -> Vec<u128> {
+        let roots: Vec<Vec<(&AccountId, u128)>> = projects
+            .iter()
+            .map(|contributions| {
+                contributions
+                    .iter()
+                    .map(|c| (&c.who, c.amount.integer_sqrt()))
+                    .collect()
+            })
+            .collect();
+
+        // k_ij, how much each pair of voters supports the same projects
+        let mut coordination: BTreeMap<(&AccountId, &AccountId), u128> = BTreeMap::new();
+        for_each_pair(&roots, |pair, product| {
+            let k = coordination.entry(pair).or_insert(0);
+            *k = k.saturating_add(product);
+        });
+
+        roots
+            .iter()
+            .map(|project| {
+                let mut weight = 0u128;
+                for_each_pair(sp_std::slice::from_ref(project), |pair, product| {
+                    let k = coordination.get(&pair).copied().unwrap_or_default();
+                    let subsidy = multiply_by_rational(
+                        product,
+                        self.coordination,
+                        self.coordination.saturating_add(k),
+                    )
+                    .unwrap_or_default();
+                    weight = weight.saturating_add(subsidy);
+                });
+                weight
+            })
+            .collect()
+    }
+}
+
+/// Share `pool` between projects in proportion to their `weights`, nothing is shared when all
+/// weights are zero.
+pub fn distribute(pool: u128, weights: &[u128]) -> Vec<u128> {
+    let total = weights
+        .iter()
+        .fold(0u128, |acc, weight| acc.saturating_add(*weight));
+    weights
+        .iter()
+        .map(|weight| multiply_by_rational(pool, *weight, total).unwrap_or_default())
+        .collect()
+}
+
+/// Like [`distribute`], but no project receives more than `cap` of the pool. The excess of the
+/// capped projects is shared again between the remaining ones until nobody exceeds the cap, what
+/// can not be shared (every project capped) is left undistributed.
+pub fn distribute_capped(pool: u128, weights: &[u128], cap: Perbill) -> Vec<u128> {
+    let max_match = cap * pool;
+    let mut funds = vec![0u128; weights.len()];
+    let mut capped = vec![false; weights.len()];
+    let mut remaining = pool;
+    loop {
+        let open: Vec<usize> = (0..weights.len()).filter(|i| !capped[*i]).collect();
+        let open_weights: Vec<u128> = open.iter().map(|i| weights[*i]).collect();
+        let shares = distribute(remaining, &open_weights);
+        let mut newly_capped = false;
+        for (i, share) in open.iter().zip(shares.iter()) {
+            if *share > max_match {
+                capped[*i] = true;
+                funds[*i] = max_match;
+                remaining = remaining.saturating_sub(max_match);
+                newly_capped = true;
+            }
+        }
+        if !newly_capped {
+            for (i, share) in open.into_iter().zip(shares.into_iter()) {
+                funds[i] = share;
+            }
+            return funds;
+        }
+    }
+}
+
+/// `(sum of x)^2 - sum of x^2`, which is twice the sum of the cross products.
+fn cross_products(values: impl Iterator<Item = u128>) -> u128 {
+    let (sum, sum_of_squares) = values.fold((0u128, 0u128), |(sum, squares), x| {
+        (
+            sum.saturating_add(x),
+            squares.saturating_add(x.saturating_mul(x)),
+        )
+    });
+    sum.saturating_mul(sum).saturating_sub(sum_of_squares)
+}
+
+/// Call `f` with every (ordered) pair of voters of each project and the product of their roots.
+fn for_each_pair<'a, AccountId: Ord>(
+    projects: &[Vec<(&'a AccountId, u128)>],
+    mut f: impl FnMut((&'a AccountId, &'a AccountId), u128),
+) {
+    for project in projects {
+        for (i, (a, root_a)) in project.iter().enumerate() {
+            for (b, root_b) in project.iter().skip(i + 1) {
+                let pair = if a <= b { (*a, *b) } else { (*b, *a) };
+                f(pair, root_a.saturating_mul(*root_b));
+            }
+        }
+    }
+}
@@ -0,0 +1,262 @@
+//! Migrations of the storage of the pallet, to be added to the `Executive` of the runtimes.
+
+use super::*;
+use frame_support::{traits::OnRuntimeUpgrade, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Move the DORA staked by the voters of the ongoing rounds from plain reserves to
+/// [`RESERVE_ID`], recording what each round reserved in `RoundReserves`. The bonds of the
+/// flags were named from the start, the first release had no flags.
+///
+/// Voters already recorded are skipped, so running it twice is harmless. It is run by
+/// [`v1::MigrateToV1`].
+pub struct MigrateToNamedReserves<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToNamedReserves<T> {
+    fn on_runtime_upgrade() -> Weight {
+        let mut reads = 0u64;
+        let mut writes = 0u64;
+        for (round_id, round) in Rounds::<T>::iter() {
+            reads += 1;
+            if !round.ongoing || round.round_reserve.is_zero() {
+                continue;
+            }
+            let reserve_num = T::ReserveUnit::get().saturating_mul(round.round_reserve);
+            for (voter, staked) in RoundParticipants::<T>::iter_prefix(round_id) {
+                reads += 2;
+                if !staked || RoundReserves::<T>::contains_key(round_id, &voter) {
+                    continue;
+                }
+                let amount: DoraBalance<T> = reserve_num.saturated_into();
+                let moved = amount.saturating_sub(T::Currency::unreserve(&voter, amount));
+                // what was just unreserved can be reserved again
+                let _ = T::Currency::reserve_named(&RESERVE_ID, &voter, moved);
+                RoundReserves::<T>::insert(round_id, &voter, moved.saturated_into::<u128>());
+                writes += 3;
+            }
+        }
+
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+}
+
+/// From the layout of the first release to version 1.
+pub mod v1 {
+    use super::*;
+    use frame_support::{
+        storage_alias,
+        traits::{GetStorageVersion, StorageVersion},
+        Blake2_128Concat,
+    };
+    use sp_runtime::traits::Hash;
+
+    /// A round before the matching, the sponsors and the settings of the voters were added.
+    #[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo, RuntimeDebug)]
+    pub struct OldRound<AccountId, BoundedString, CurrencyIdOf> {
+        pub name: BoundedString,
+        pub currency_id: CurrencyIdOf,
+        pub ongoing: bool,
+        pub support_pool: u128,
+        pub pre_tax_support_pool: u128,
+        pub total_support_area: u128,
+        pub total_tax: u128,
+        pub round_reserve: u128,
+        pub admin: AccountId,
+    }
+
+    pub(crate) type OldRoundOf<T> = OldRound<
+        <T as frame_system::Config>::AccountId,
+        BoundedVec<u8, <T as Config>::NameMaxLength>,
+        CurrencyIdOf<T>,
+    >;
+
+    #[storage_alias]
+    pub(crate) type Rounds<T: Config> = StorageMap<Pallet<T>, Blake2_128Concat, u32, OldRoundOf<T>>;
+
+    /// Ballots keyed by the hash of the project and its round, which can not be reversed
+    #[storage_alias]
+    pub(crate) type ProjectVotes<T: Config> = StorageDoubleMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        <T as frame_system::Config>::Hash,
+        Blake2_128Concat,
+        <T as frame_system::Config>::AccountId,
+        u128,
+    >;
+
+    /// Translate the rounds to the current layout with the default settings, key the ballots
+    /// by round, count the projects and voters of the rounds and move the stakes of the voters
+    /// to [`RESERVE_ID`].
+    ///
+    /// The first release sent the donations to the admin of the round, the support pool of the
+    /// ongoing rounds is moved from the admin to the round account the rounds are now paid
+    /// from and the admin becomes the sponsor of it. A pool the admin can not pay is left with
+    /// them and logged, the round then goes on with an empty pool. The projects of the ended
+    /// rounds are ranked. Does nothing once the pallet is at version 1.
+    ///
+    /// It runs in a single block, its weight is checked against the block limit by
+    /// `post_upgrade` with try-runtime.
+    pub struct MigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if Pallet::<T>::on_chain_storage_version() >= 1 {
+                return T::DbWeight::get().reads(1);
+            }
+            let mut reads = 1u64;
+            let mut writes = 1u64;
+            let mut ended = Vec::new();
+            crate::Rounds::<T>::translate::<OldRoundOf<T>, _>(|round_id, old| {
+                reads += 1;
+                writes += 1;
+                let mut support_pool = old.support_pool;
+                let mut pre_tax_support_pool = old.pre_tax_support_pool;
+                let mut sponsors = 0;
+                if !old.ongoing {
+                    ended.push(round_id);
+                } else if !old.support_pool.is_zero() {
+                    reads += 2;
+                    match T::MultiCurrency::transfer(
+                        old.currency_id,
+                        &old.admin,
+                        &Pallet::<T>::round_account(round_id),
+                        Pallet::<T>::u128_to_balance(old.support_pool),
+                    ) {
+                        Ok(()) => {
+                            Pallet::<T>::hold_in_round(
+                                round_id,
+                                old.currency_id,
+                                old.support_pool,
+                                old.support_pool,
+                            );
+                            // the admin sponsored what the pool held, what is not
+                            // distributed is refunded to it like to the other sponsors
+                            crate::Sponsorships::<T>::insert(
+                                round_id,
+                                &old.admin,
+                                Sponsorship {
+                                    amount: old.support_pool,
+                                    conditions: Default::default(),
+                                    void: false,
+                                    refunded: false,
+                                },
+                            );
+                            sponsors = 1;
+                            writes += 5;
+                        }
+                        // the pool stays with the admin, the round goes on from an empty one
+                        Err(error) => {
+                            log::warn!(
+                                target: "runtime::quadratic-funding",
+                                "the pool of round {} could not be moved from its admin: {:?}",
+                                round_id,
+                                error,
+                            );
+                            support_pool = 0;
+                            pre_tax_support_pool = 0;
+                        }
+                    }
+                }
+                Some(Round {
+                    name: old.name,
+                    currency_id: old.currency_id,
+                    ongoing: old.ongoing,
+                    support_pool,
+                    pre_tax_support_pool,
+                    total_support_area: old.total_support_area,
+                    total_tax: old.total_tax,
+                    round_reserve: old.round_reserve,
+                    admin: old.admin,
+                    matching: MatchingMode::default(),
+                    max_match_per_project: Perbill::one(),
+                    sponsors,
+                    undistributed_pool: 0,
+                    eligibility: None,
+                    limits: VoterLimits::default(),
+                    cost_curve: CostCurve::default(),
+                })
+            });
+            for (round_id, hash) in Projects::<T>::iter_keys() {
+                reads += 2;
+                writes += 1;
+                RoundSizes::<T>::mutate(round_id, |size| size.projects.saturating_inc());
+                let vote_hash = T::Hashing::hash_of(&(&hash, &round_id));
+                let mut voters = 0u32;
+                for (who, ballots) in ProjectVotes::<T>::drain_prefix(vote_hash) {
+                    crate::ProjectVotes::<T>::insert((round_id, hash, who), ballots);
+                    voters.saturating_inc();
+                    reads += 1;
+                    writes += 2;
+                }
+                if voters > 0 {
+                    // the rounds started before the bound may have more voters than it allows
+                    ProjectVoters::<T>::insert(round_id, hash, voters);
+                    RoundSizes::<T>::mutate(round_id, |size| {
+                        size.voters_per_project = size.voters_per_project.max(voters);
+                        size.votes = size.votes.saturating_add(voters);
+                    });
+                    writes += 1;
+                }
+            }
+            for (round_id, _) in RoundParticipants::<T>::iter_keys() {
+                RoundSizes::<T>::mutate(round_id, |size| size.participants.saturating_inc());
+                reads += 2;
+                writes += 1;
+            }
+            // the rounds ended by the first release are ranked for the badges of their projects
+            for round_id in ended {
+                let projects = Pallet::<T>::rank_projects(round_id) as u64;
+                reads += projects;
+                writes += projects;
+            }
+            StorageVersion::new(1).put::<Pallet<T>>();
+            let weight = T::DbWeight::get()
+                .reads_writes(reads, writes)
+                .saturating_add(MigrateToNamedReserves::<T>::on_runtime_upgrade());
+            #[cfg(feature = "try-runtime")]
+            {
+                use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+                Self::set_temp_storage(weight, "weight");
+            }
+            weight
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<(), &'static str> {
+            use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 0,
+                "QuadraticFunding is already at version 1"
+            );
+            Self::set_temp_storage(Rounds::<T>::iter().count() as u32, "rounds");
+            Self::set_temp_storage(ProjectVotes::<T>::iter().count() as u32, "votes");
+            Ok(())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade() -> Result<(), &'static str> {
+            use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 1,
+                "QuadraticFunding was not migrated to version 1"
+            );
+            let rounds: u32 = Self::get_temp_storage("rounds").ok_or("rounds not counted")?;
+            ensure!(
+                crate::Rounds::<T>::iter().count() as u32 == rounds,
+                "rounds lost in the migration"
+            );
+            let votes: u32 = Self::get_temp_storage("votes").ok_or("votes not counted")?;
+            ensure!(
+                crate::ProjectVotes::<T>::iter().count() as u32 == votes,
+                "ballots of projects which do not exist"
+            );
+            // the migration runs in a single block, it must fit in one
+            let weight: Weight = Self::get_temp_storage("weight").ok_or("weight not recorded")?;
+            ensure!(
+                weight <= <T as frame_system::Config>::BlockWeights::get().max_block,
+                "the migration does not fit in a block"
+            );
+            Ok(())
+        }
+    }
+}
@@ -1,6 +1,9 @@
 //! Test utilities
 
-use crate::{self as pallet_qf, Config, Event as QFEvent};
+use crate::{
+    self as pallet_qf, Config, Event as QFEvent, OnRoundEnded, OnRoundStarted, OnVote,
+    ProjectResult,
+};
 use cumulus_primitives_core::relay_chain::BlockNumber as RelayChainBlockNumber;
 use cumulus_primitives_core::PersistedValidationData;
 use cumulus_primitives_parachain_inherent::ParachainInherentData;
@@ -9,19 +12,22 @@ use frame_support::{
     dispatch::UnfilteredDispatchable,
     inherent::{InherentData, ProvideInherent},
     parameter_types,
-    traits::{ConstBool, ConstU32, GenesisBuild, Nothing, OnFinalize, OnInitialize},
+    traits::{
+        AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU32, GenesisBuild, Nothing, OnFinalize,
+        OnInitialize,
+    },
     PalletId,
 };
-use frame_system::{EnsureRoot, RawOrigin};
+use frame_system::{EnsureRoot, EnsureSigned, RawOrigin};
 use orml_currencies::BasicCurrencyAdapter;
-use orml_traits::parameter_type_with_key;
+use orml_traits::{parameter_type_with_key, DataProvider};
 use primitives::{AccountId, Amount, BlockNumber, CurrencyId, DOLLARS};
 use sp_core::H256;
 use sp_io;
 use sp_runtime::{
-    testing::Header,
+    testing::{Header, TestSignature, UintAuthorityId},
     traits::{AccountIdConversion, BlakeTwo256, IdentityLookup},
-    Perbill,
+    FixedPointNumber, FixedU128, Perbill,
 };
 use sp_std::convert::{From, TryInto};
 pub type Balance = u128;
@@ -43,6 +49,7 @@ construct_runtime!(
         Currencies: orml_currencies::{Pallet, Call},
         Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
         ParachainSystem: cumulus_pallet_parachain_system::{Pallet, Call, Storage, Inherent, Event<T>},
+        Uniques: pallet_uniques::{Pallet, Call, Storage, Event<T>},
     }
 );
 
@@ -100,8 +107,8 @@ parameter_types! {
 }
 
 impl pallet_balances::Config for Runtime {
-    type MaxReserves = ();
-    type ReserveIdentifier = [u8; 8];
+    type MaxReserves = ConstU32<50>;
+    type ReserveIdentifier = primitives::ReserveIdentifier;
     type MaxLocks = ();
     type Balance = Balance;
     type Event = Event;
@@ -152,6 +159,28 @@ impl orml_currencies::Config for Runtime {
 // pub type NativeCurrency = NativeCurrencyOf<Runtime>;
 pub type AdaptedBasicCurrency = BasicCurrencyAdapter<Runtime, Balances, i64, u64>;
 
+impl pallet_uniques::Config for Runtime {
+    type Event = Event;
+    type CollectionId = u32;
+    type ItemId = u32;
+    type Currency = Balances;
+    type ForceOrigin = EnsureRoot<u64>;
+    type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<u64>>;
+    // badges can not be transferred
+    type Locker = QuadraticFunding;
+    type CollectionDeposit = ConstU128<0>;
+    type ItemDeposit = ConstU128<0>;
+    type MetadataDepositBase = ConstU128<0>;
+    type AttributeDepositBase = ConstU128<0>;
+    type DepositPerByte = ConstU128<0>;
+    type StringLimit = ConstU32<50>;
+    type KeyLimit = ConstU32<50>;
+    type ValueLimit = ConstU32<50>;
+    type WeightInfo = ();
+    #[cfg(feature = "runtime-benchmarks")]
+    type Helper = ();
+}
+
 // Configure the pallet-qf in pallets/quadratic-funding.
 parameter_types! {
     // pow(10,12) => Unit, for easy fee control, we use pow(10,9)
@@ -166,6 +195,56 @@ parameter_types! {
     pub const AppId: u8 = 1;
     // minimal number of units to reserve to get qualified to vote
     pub const ReserveUnit: u128 = 1000000000000;
+    pub static MaxSponsors: u32 = 100;
+    pub const MinSponsorship: u128 = DOLLARS;
+    pub const TreasuryAccount: u64 = 6;
+    pub const MaxMilestones: u32 = 5;
+    pub const MilestoneDeadline: u64 = 100;
+    pub const ChallengeBond: u128 = 10 * DOLLARS;
+    pub const ArchiveDelay: u64 = 100;
+    pub const MaxVotedProjects: u32 = 2;
+    pub static MaxVotersPerProject: u32 = 100;
+    pub static MaxProjectsPerRound: u32 = 100;
+    pub static MaxVotesPerRound: u32 = 1_000;
+    pub const MaxRoundCurrencies: u32 = 1;
+    pub const BadgeCollection: u32 = 7;
+    pub static KsmPrice: Option<FixedU128> = Some(FixedU128::saturating_from_integer(20u128));
+    pub static StartedRounds: Vec<(u32, u64)> = vec![];
+    pub static CountedVotes: Vec<(u32, H256, u64, u128, u128)> = vec![];
+    pub static EndedRounds: Vec<(u32, Vec<ProjectResult<u64, H256>>)> = vec![];
+}
+
+/// DORA is worth 1, KSM is worth `KsmPrice`
+pub struct MockPriceFeed;
+
+impl DataProvider<CurrencyId, FixedU128> for MockPriceFeed {
+    fn get(currency_id: &CurrencyId) -> Option<FixedU128> {
+        match currency_id {
+            CurrencyId::DORA => Some(FixedU128::saturating_from_integer(1u128)),
+            CurrencyId::KSM => KsmPrice::get(),
+        }
+    }
+}
+
+/// Records the calls of the hooks
+pub struct RecordHooks;
+
+impl OnRoundStarted<u64> for RecordHooks {
+    fn on_round_started(round_id: u32, admin: &u64) {
+        StartedRounds::mutate(|rounds| rounds.push((round_id, *admin)));
+    }
+}
+
+impl OnVote<u64, H256> for RecordHooks {
+    fn on_vote(round_id: u32, project: &H256, voter: &u64, ballot: u128, amount: u128) {
+        CountedVotes::mutate(|votes| votes.push((round_id, *project, *voter, ballot, amount)));
+    }
+}
+
+impl OnRoundEnded<u64, H256> for RecordHooks {
+    fn on_round_ended(round_id: u32, results: &[ProjectResult<u64, H256>]) {
+        EndedRounds::mutate(|rounds| rounds.push((round_id, results.to_vec())));
+    }
 }
 
 // qf pallet config
@@ -176,6 +255,8 @@ impl Config for Runtime {
     type PalletId = QuadraticFundingPalletId;
     // Origin who can control the round
     type AdminOrigin = EnsureRoot<u64>;
+    type TreasuryOrigin = EnsureRoot<u64>;
+    type TreasuryAccount = TreasuryAccount;
     // Use the UnitOfVote from the parameter_types block.
     type UnitOfVote = VoteUnit;
     // Use the MinNickLength from the parameter_types block.
@@ -187,6 +268,28 @@ impl Config for Runtime {
     // The maximum length of project name
     type NameMaxLength = NameMaxLength;
     type ReserveUnit = ReserveUnit;
+    type MaxSponsors = MaxSponsors;
+    type MinSponsorship = MinSponsorship;
+    type MilestoneReviewerOrigin = EnsureRoot<u64>;
+    type MaxMilestones = MaxMilestones;
+    type MilestoneDeadline = MilestoneDeadline;
+    type ChallengeOrigin = EnsureRoot<u64>;
+    type ResolutionOrigin = EnsureRoot<u64>;
+    type ChallengeBond = ChallengeBond;
+    type ArchiveDelay = ArchiveDelay;
+    type MaxVotedProjects = MaxVotedProjects;
+    type MaxVotersPerProject = MaxVotersPerProject;
+    type MaxProjectsPerRound = MaxProjectsPerRound;
+    type MaxVotesPerRound = MaxVotesPerRound;
+    type PriceFeed = MockPriceFeed;
+    type MaxRoundCurrencies = MaxRoundCurrencies;
+    type OffchainSignature = TestSignature;
+    type OffchainPublic = UintAuthorityId;
+    type OnRoundStarted = RecordHooks;
+    type OnVote = RecordHooks;
+    type OnRoundEnded = RecordHooks;
+    type Badges = crate::badges::UniquesBadges<Runtime>;
+    type BadgeCollection = BadgeCollection;
     type WeightInfo = ();
 }
 
@@ -201,6 +304,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
             (2, 100 * DOLLARS),
             (3, 100 * DOLLARS),
             (4, 1_000_000_000_000),
+            (TreasuryAccount::get(), 100 * DOLLARS),
         ],
     }
     .assimilate_storage(&mut t)
@@ -0,0 +1,55 @@
+//! Prices of the currencies a round accepts besides its own.
+//!
+//! Everything a round accounts for (ballot costs, support pool, grants) is valued in the currency
+//! of the round. Contributions made in another accepted currency are converted with a rate, the
+//! value of one unit of that currency in units of the round currency.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use orml_traits::DataProvider;
+use scale_info::TypeInfo;
+use sp_runtime::{
+    helpers_128bit::multiply_by_rational, traits::CheckedDiv, FixedPointNumber, FixedU128,
+    RuntimeDebug,
+};
+
+/// How the rate of an accepted currency is obtained.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub enum CurrencyRate {
+    /// A rate set by governance.
+    Fixed(FixedU128),
+    /// The ratio of the prices of both currencies given by the price feed, e.g. `orml_oracle`.
+    Oracle,
+}
+
+/// A price feed without any price, for runtimes which only use fixed rates.
+pub struct NoPriceFeed;
+
+impl<CurrencyId> DataProvider<CurrencyId, FixedU128> for NoPriceFeed {
+    fn get(_key: &CurrencyId) -> Option<FixedU128> {
+        None
+    }
+}
+
+/// The rate of `currency_id` in units of `base` according to `Feed`.
+pub fn oracle_rate<CurrencyId, Feed: DataProvider<CurrencyId, FixedU128>>(
+    currency_id: &CurrencyId,
+    base: &CurrencyId,
+) -> Option<FixedU128> {
+    Feed::get(currency_id)?.checked_div(&Feed::get(base)?)
+}
+
+/// Value of `amount` units of a currency in units of the round currency.
+pub fn to_base(rate: FixedU128, amount: u128) -> u128 {
+    rate.saturating_mul_int(amount)
+}
+
+/// Units of a currency worth `value` units of the round currency, rounded up so that voters
+/// never pay less than the value of their ballots.
+pub fn from_base(rate: FixedU128, value: u128) -> Option<u128> {
+    let amount = multiply_by_rational(value, FixedU128::DIV, rate.into_inner()).ok()?;
+    if to_base(rate, amount) < value {
+        amount.checked_add(1)
+    } else {
+        Some(amount)
+    }
+}
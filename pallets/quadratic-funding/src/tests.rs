@@ -1,10 +1,30 @@
-use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok, dispatch::DispatchError, PalletId};
+use crate::{
+    cost::{CappedQuadratic, Exponential, Linear, Quadratic},
+    matching::{self, Clr, Contribution, MatchingFormula, PairwiseBounded, SupportArea},
+    migrations::{v1, MigrateToNamedReserves},
+    mock::*,
+    Badge, BadgeKind, Conviction, CostCurve, CurrencyRate, EligibilityRule, Error, FeeDestination,
+    FlagResolution, MatchingMode, QuadraticFundingInterface, RoundTemplate, RoundWitness,
+    SponsorConditions, VestingSchedule, VoteCostCurve, VotePayload, VoterLimits, WeightInfo,
+    RESERVE_ID, VOTE_SIGNING_CONTEXT,
+};
+use codec::Encode;
+use frame_support::{
+    assert_noop, assert_ok,
+    dispatch::{DispatchError, GetDispatchInfo},
+    traits::{
+        tokens::nonfungibles::Inspect, GetStorageVersion, Hooks, NamedReservableCurrency,
+        OnRuntimeUpgrade, ReservableCurrency, StorageVersion,
+    },
+    PalletId,
+};
+use orml_traits::MultiCurrency;
 use primitives::{currency::CurrencyId, DOLLARS};
 use serde::de::Unexpected::Option;
 use sp_runtime::traits::{
     AccountIdConversion, BlakeTwo256, Hash, IdentifyAccount, UniqueSaturatedFrom,
 };
+use sp_runtime::{testing::TestSignature, FixedPointNumber, FixedU128, Perbill};
 use std::ops::Sub;
 use std::ptr::hash;
 
@@ -129,9 +149,13 @@ fn test_donate_works() {
             donate_amount
         );
 
-        // donate amount in admin account
+        // donate amount in round account until the round ends
         assert_eq!(
-            Balances::free_balance(QuadraticFunding::round_admin_account(round_id)),
+            Balances::free_balance(QuadraticFunding::round_account(round_id)),
+            donate_amount - 6u128.checked_mul(donate_amount / 1000).unwrap()
+        );
+        assert_eq!(
+            QuadraticFunding::sponsorships(round_id, 1).unwrap().amount,
             donate_amount - 6u128.checked_mul(donate_amount / 1000).unwrap()
         );
         // fee amount in pallet account
@@ -171,7 +195,11 @@ fn test_donate_ended_round() {
             1,
             2
         ));
-        assert_ok!(QuadraticFunding::end_round(Origin::root(), round_id));
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            round_id,
+            QuadraticFunding::round_witness(round_id)
+        ));
 
         assert_noop!(
             QuadraticFunding::donate(
@@ -340,7 +368,11 @@ fn test_register_project_ended_round() {
             1,
             2
         ));
-        assert_ok!(QuadraticFunding::end_round(Origin::root(), round_id));
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            round_id,
+            QuadraticFunding::round_witness(round_id)
+        ));
 
         let project_hash = BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 0u128));
         assert_noop!(
@@ -388,6 +420,77 @@ fn test_register_project_with_same_name() {
     })
 }
 
+#[test]
+fn test_register_project_over_the_limit() {
+    new_test_ext().execute_with(|| {
+        MaxProjectsPerRound::set(2);
+        assert_ok!(QuadraticFunding::start_round(
+            Origin::root(),
+            1,
+            CurrencyId::DORA,
+            "doraRound".to_string().into(),
+            1,
+            2
+        ));
+        for i in 0..2u128 {
+            assert_ok!(QuadraticFunding::register_project(
+                Origin::signed(2),
+                1,
+                BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), i)),
+                "project".to_string().into()
+            ));
+        }
+        assert_noop!(
+            QuadraticFunding::register_project(
+                Origin::signed(3),
+                1,
+                BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 2u128)),
+                "project".to_string().into()
+            ),
+            Error::<Runtime>::TooManyProjects
+        );
+        assert_eq!(QuadraticFunding::round_size(1).projects, 2);
+    })
+}
+
+#[test]
+fn test_vote_over_the_round_limit() {
+    new_test_ext().execute_with(|| {
+        MaxVotesPerRound::set(2);
+        let round_id = 1;
+        let project_hash = start_milestone_round(round_id);
+        for who in [1, 2] {
+            assert_ok!(QuadraticFunding::vote(
+                Origin::signed(who),
+                CurrencyId::DORA,
+                round_id,
+                project_hash,
+                1
+            ));
+        }
+        // more ballots for a voted project are not another vote
+        assert_ok!(QuadraticFunding::vote(
+            Origin::signed(2),
+            CurrencyId::DORA,
+            round_id,
+            project_hash,
+            1
+        ));
+        assert_noop!(
+            QuadraticFunding::vote(
+                Origin::signed(3),
+                CurrencyId::DORA,
+                round_id,
+                project_hash,
+                1
+            ),
+            Error::<Runtime>::TooManyVotes
+        );
+        assert_eq!(QuadraticFunding::round_size(round_id).votes, 2);
+        assert_eq!(QuadraticFunding::round_size(round_id).participants, 2);
+    })
+}
+
 #[test]
 fn test_register_project_in_diff_round_with_same_hash() {
     // 同样的hash可以参加不同的round
@@ -474,12 +577,13 @@ fn test_vote_works() {
         // vote amount in admin account
         assert_eq!(
             Balances::free_balance(QuadraticFunding::round_admin_account(round_id)),
-            (1 + 2 + 3) * 1_000_000_000_000 - QuadraticFunding::cal_amount((1 + 2 + 3), true)
+            (1 + 2 + 3) * 1_000_000_000_000
+                - QuadraticFunding::cal_amount((1 + 2 + 3), true).unwrap()
         );
         // fee amount in pallet account
         assert_eq!(
             Balances::free_balance(QuadraticFunding::account_id()),
-            QuadraticFunding::cal_amount((1 + 2 + 3), true)
+            QuadraticFunding::cal_amount((1 + 2 + 3), true).unwrap()
         );
         assert_ok!(QuadraticFunding::vote(
             Origin::signed(3),
@@ -496,7 +600,7 @@ fn test_vote_works() {
         assert_eq!(
             Balances::free_balance(QuadraticFunding::round_admin_account(round_id)),
             (1 + 2 + 3 + 4) * 1_000_000_000_000
-                - QuadraticFunding::cal_amount((1 + 2 + 3 + 4), true)
+                - QuadraticFunding::cal_amount((1 + 2 + 3 + 4), true).unwrap()
         );
     })
 }
@@ -669,7 +773,11 @@ fn test_vote_ended_round() {
             1,
             2
         ));
-        assert_ok!(QuadraticFunding::end_round(Origin::root(), round_id));
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            round_id,
+            QuadraticFunding::round_witness(round_id)
+        ));
 
         let project_hash = BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 0u128));
         let ballot_count = 3;
@@ -844,7 +952,11 @@ fn test_end_round_works() {
             2
         ));
 
-        assert_ok!(QuadraticFunding::end_round(Origin::root(), 1));
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            1,
+            QuadraticFunding::round_witness(1)
+        ));
         assert_eq!(QuadraticFunding::rounds(1).unwrap().ongoing, false);
     })
 }
@@ -855,7 +967,11 @@ fn test_end_inexist_round() {
         let inexist_round_id = 1;
 
         assert_noop!(
-            QuadraticFunding::end_round(Origin::root(), inexist_round_id),
+            QuadraticFunding::end_round(
+                Origin::root(),
+                inexist_round_id,
+                QuadraticFunding::round_witness(inexist_round_id)
+            ),
             Error::<Runtime>::RoundNotExist
         );
     })
@@ -874,10 +990,18 @@ fn test_end_ended_round() {
             1,
             2
         ));
-        assert_ok!(QuadraticFunding::end_round(Origin::root(), round_id));
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            round_id,
+            QuadraticFunding::round_witness(round_id)
+        ));
 
         assert_noop!(
-            QuadraticFunding::end_round(Origin::root(), round_id),
+            QuadraticFunding::end_round(
+                Origin::root(),
+                round_id,
+                QuadraticFunding::round_witness(round_id)
+            ),
             Error::<Runtime>::RoundHasEnded
         );
     })
@@ -897,7 +1021,11 @@ fn test_end_round_must_be_root() {
         ));
 
         assert_noop!(
-            QuadraticFunding::end_round(Origin::signed(1), round_id),
+            QuadraticFunding::end_round(
+                Origin::signed(1),
+                round_id,
+                QuadraticFunding::round_witness(round_id)
+            ),
             DispatchError::BadOrigin
         );
     })
@@ -945,15 +1073,3718 @@ fn test_a_complete_round_works() {
             project_hash,
             3
         ));
-        assert_ok!(QuadraticFunding::end_round(Origin::root(), round_id));
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            round_id,
+            QuadraticFunding::round_witness(round_id)
+        ));
         assert_eq!(QuadraticFunding::rounds(round_id).unwrap().ongoing, false);
         assert_eq!(
             Balances::free_balance(QuadraticFunding::round_admin_account(round_id)),
             donate_amount - 6u128.checked_mul(donate_amount / 1000).unwrap()
                 + (1 + 2 + 3) * 1_000_000_000_000
-                - QuadraticFunding::cal_amount(1 + 2 + 3, true)
+                - QuadraticFunding::cal_amount(1 + 2 + 3, true).unwrap()
                 + (1 + 2) * 1_000_000_000_000
-                - QuadraticFunding::cal_amount(1 + 2, true)
+                - QuadraticFunding::cal_amount(1 + 2, true).unwrap()
+        );
+    })
+}
+
+fn contributions(votes: &[(u64, u128, u128)]) -> Vec<Contribution<u64>> {
+    votes
+        .iter()
+        .map(|(who, ballots, amount)| Contribution {
+            who: *who,
+            ballots: *ballots,
+            amount: *amount,
+        })
+        .collect()
+}
+
+#[test]
+fn test_support_area_formula_reference_vectors() {
+    let projects = vec![
+        contributions(&[(1, 2, 0), (2, 3, 0)]),
+        contributions(&[(1, 1, 0), (2, 1, 0), (3, 1, 0), (4, 1, 0)]),
+        contributions(&[(1, 7, 0)]),
+        vec![],
+    ];
+    assert_eq!(SupportArea.weights(&projects), vec![6, 6, 0, 0]);
+}
+
+#[test]
+fn test_clr_formula_reference_vectors() {
+    let projects = vec![
+        // (2 + 3)^2 - (4 + 9)
+        contributions(&[(1, 0, 4), (2, 0, 9)]),
+        // (1 + 1 + 1 + 1)^2 - 4
+        contributions(&[(1, 0, 1), (2, 0, 1), (3, 0, 1), (4, 0, 1)]),
+        // a single contributor is never matched
+        contributions(&[(1, 0, 100)]),
+    ];
+    assert_eq!(Clr.weights(&projects), vec![12, 12, 0]);
+}
+
+#[test]
+fn test_pairwise_bounded_formula_reference_vectors() {
+    // 1 and 2 support both project 0 and 1, so their pair is bounded twice as much as the pair
+    // of 3 and 4 which only meets on project 2
+    let projects = vec![
+        contributions(&[(1, 0, 4), (2, 0, 9)]),
+        contributions(&[(2, 0, 9), (1, 0, 4)]),
+        contributions(&[(3, 0, 16), (4, 0, 1)]),
+    ];
+    // k_12 = 6 + 6, 6 * 12 / (12 + 12) = 3
+    // k_34 = 4, 4 * 12 / (12 + 4) = 3
+    assert_eq!(
+        PairwiseBounded { coordination: 12 }.weights(&projects),
+        vec![3, 3, 3]
+    );
+    assert_eq!(
+        MatchingMode::PairwiseBounded(12).weights(&projects),
+        vec![3, 3, 3]
+    );
+    // without coordination budget nothing is matched
+    assert_eq!(
+        PairwiseBounded { coordination: 0 }.weights(&projects),
+        vec![0, 0, 0]
+    );
+}
+
+#[test]
+fn test_distribute_matching_pool() {
+    assert_eq!(matching::distribute(100, &[1, 3]), vec![25, 75]);
+    assert_eq!(matching::distribute(100, &[1, 1, 1]), vec![33, 33, 33]);
+    assert_eq!(matching::distribute(100, &[0, 0]), vec![0, 0]);
+    assert_eq!(
+        matching::distribute(u128::MAX, &[u128::MAX, u128::MAX]),
+        vec![u128::MAX / 2, u128::MAX / 2]
+    );
+}
+
+#[test]
+fn test_set_matching_mode_works() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(QuadraticFunding::start_round(
+            Origin::root(),
+            1,
+            CurrencyId::DORA,
+            "doraRound".to_string().into(),
+            1,
+            2
+        ));
+        assert_eq!(
+            QuadraticFunding::rounds(1).unwrap().matching,
+            MatchingMode::SupportArea
+        );
+        assert_noop!(
+            QuadraticFunding::set_matching_mode(Origin::signed(1), 1, MatchingMode::Clr),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            QuadraticFunding::set_matching_mode(Origin::root(), 2, MatchingMode::Clr),
+            Error::<Runtime>::RoundNotExist
+        );
+        assert_noop!(
+            QuadraticFunding::set_matching_mode(
+                Origin::root(),
+                1,
+                MatchingMode::PairwiseBounded(0)
+            ),
+            Error::<Runtime>::InvalidMatchingMode
+        );
+        assert_ok!(QuadraticFunding::set_matching_mode(
+            Origin::root(),
+            1,
+            MatchingMode::Clr
+        ));
+        assert_eq!(
+            QuadraticFunding::rounds(1).unwrap().matching,
+            MatchingMode::Clr
+        );
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            1,
+            QuadraticFunding::round_witness(1)
+        ));
+        assert_noop!(
+            QuadraticFunding::set_matching_mode(Origin::root(), 1, MatchingMode::SupportArea),
+            Error::<Runtime>::RoundHasEnded
+        );
+    })
+}
+
+#[test]
+fn test_end_round_uses_matching_mode() {
+    for mode in [
+        MatchingMode::SupportArea,
+        MatchingMode::Clr,
+        MatchingMode::PairwiseBounded(1_000_000_000_000),
+    ] {
+        new_test_ext().execute_with(|| {
+            let round_id = 1;
+            assert_ok!(QuadraticFunding::start_round(
+                Origin::root(),
+                round_id,
+                CurrencyId::DORA,
+                "doraRound".to_string().into(),
+                5,
+                0
+            ));
+            assert_ok!(QuadraticFunding::set_matching_mode(
+                Origin::root(),
+                round_id,
+                mode
+            ));
+            assert_ok!(QuadraticFunding::donate(
+                Origin::signed(1),
+                round_id,
+                1_000_000_000_000_000,
+                CurrencyId::DORA,
+            ));
+            let project_0 = BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 0u128));
+            let project_1 = BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 1u128));
+            for hash in [project_0, project_1] {
+                assert_ok!(QuadraticFunding::register_project(
+                    Origin::signed(1),
+                    round_id,
+                    hash,
+                    "project".to_string().into()
+                ));
+            }
+            for (who, hash, ballot) in [
+                (2, project_0, 1),
+                (3, project_0, 1),
+                (2, project_1, 2),
+                (3, project_1, 1),
+            ] {
+                assert_ok!(QuadraticFunding::vote(
+                    Origin::signed(who),
+                    CurrencyId::DORA,
+                    round_id,
+                    hash,
+                    ballot
+                ));
+            }
+            let round = QuadraticFunding::rounds(round_id).unwrap();
+            let weights = mode.weights(&[
+                QuadraticFunding::project_contributions(round_id, project_0),
+                QuadraticFunding::project_contributions(round_id, project_1),
+            ]);
+            let expected = matching::distribute(round.support_pool, &weights);
+            assert_ok!(QuadraticFunding::end_round(
+                Origin::root(),
+                round_id,
+                QuadraticFunding::round_witness(round_id)
+            ));
+
+            let fund_0 = QuadraticFunding::projects(round_id, project_0)
+                .unwrap()
+                .support_fund;
+            let fund_1 = QuadraticFunding::projects(round_id, project_1)
+                .unwrap()
+                .support_fund;
+            assert_eq!(vec![fund_0, fund_1], expected);
+            // project 1 got more support than project 0 with every formula
+            assert!(fund_0 < fund_1);
+            assert!(fund_0 + fund_1 <= round.support_pool);
+        })
+    }
+}
+
+#[test]
+fn test_distribute_capped_matching_pool() {
+    // without a binding cap this is a plain distribution
+    assert_eq!(
+        matching::distribute_capped(100, &[1, 3], Perbill::one()),
+        vec![25, 75]
+    );
+    // the excess of project 0 is shared by the others
+    assert_eq!(
+        matching::distribute_capped(100, &[8, 1, 1], Perbill::from_percent(50)),
+        vec![50, 25, 25]
+    );
+    // redistributing can push another project over the cap
+    assert_eq!(
+        matching::distribute_capped(100, &[6, 3, 1], Perbill::from_percent(40)),
+        vec![40, 40, 20]
+    );
+    // when every project is capped the rest stays in the pool
+    assert_eq!(
+        matching::distribute_capped(100, &[6, 3, 1], Perbill::from_percent(10)),
+        vec![10, 10, 10]
+    );
+    // projects without support are never matched
+    assert_eq!(
+        matching::distribute_capped(100, &[1, 0], Perbill::from_percent(50)),
+        vec![50, 0]
+    );
+}
+
+#[test]
+fn test_end_round_caps_match_per_project() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        assert_ok!(QuadraticFunding::start_round(
+            Origin::root(),
+            round_id,
+            CurrencyId::DORA,
+            "doraRound".to_string().into(),
+            5,
+            0
+        ));
+        assert_noop!(
+            QuadraticFunding::set_max_match_per_project(
+                Origin::signed(1),
+                round_id,
+                Perbill::from_percent(60)
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(QuadraticFunding::set_max_match_per_project(
+            Origin::root(),
+            round_id,
+            Perbill::from_percent(60)
+        ));
+        assert_ok!(QuadraticFunding::donate(
+            Origin::signed(1),
+            round_id,
+            1_000_000_000_000_000,
+            CurrencyId::DORA,
+        ));
+        let project_0 = BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 0u128));
+        let project_1 = BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 1u128));
+        for hash in [project_0, project_1] {
+            assert_ok!(QuadraticFunding::register_project(
+                Origin::signed(1),
+                round_id,
+                hash,
+                "project".to_string().into()
+            ));
+        }
+        // support area: project 0 => 1, project 1 => 2
+        for (who, hash, ballot) in [
+            (2, project_0, 1),
+            (3, project_0, 1),
+            (2, project_1, 2),
+            (3, project_1, 1),
+        ] {
+            assert_ok!(QuadraticFunding::vote(
+                Origin::signed(who),
+                CurrencyId::DORA,
+                round_id,
+                hash,
+                ballot
+            ));
+        }
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            round_id,
+            QuadraticFunding::round_witness(round_id)
+        ));
+
+        let pool = QuadraticFunding::rounds(round_id).unwrap().support_pool;
+        // project 1 would get 2/3 of the pool, but is capped to 60%
+        assert_eq!(
+            QuadraticFunding::projects(round_id, project_1)
+                .unwrap()
+                .support_fund,
+            Perbill::from_percent(60) * pool
+        );
+        assert_eq!(
+            QuadraticFunding::projects(round_id, project_0)
+                .unwrap()
+                .support_fund,
+            pool - Perbill::from_percent(60) * pool
+        );
+    })
+}
+
+fn start_sponsored_round(round_id: u32) {
+    assert_ok!(QuadraticFunding::start_round(
+        Origin::root(),
+        round_id,
+        CurrencyId::DORA,
+        "doraRound".to_string().into(),
+        5,
+        0
+    ));
+}
+
+#[test]
+fn test_refund_sponsors_of_round_without_votes() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let donate_amount = 1_000_000_000_000_000;
+        let net_amount = donate_amount - 6u128.checked_mul(donate_amount / 1000).unwrap();
+        start_sponsored_round(round_id);
+        assert_ok!(QuadraticFunding::donate(
+            Origin::signed(1),
+            round_id,
+            donate_amount,
+            CurrencyId::DORA,
+        ));
+        assert_ok!(QuadraticFunding::donate(
+            Origin::signed(2),
+            round_id,
+            donate_amount,
+            CurrencyId::DORA,
+        ));
+        assert_ok!(QuadraticFunding::donate(
+            Origin::signed(2),
+            round_id,
+            donate_amount,
+            CurrencyId::DORA,
+        ));
+        assert_eq!(QuadraticFunding::rounds(round_id).unwrap().sponsors, 2);
+        assert_eq!(
+            QuadraticFunding::sponsorships(round_id, 2).unwrap().amount,
+            2 * net_amount
+        );
+        assert_noop!(
+            QuadraticFunding::refund_sponsor(Origin::signed(3), round_id, 1),
+            Error::<Runtime>::RoundIsOngoing
+        );
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            round_id,
+            QuadraticFunding::round_witness(round_id)
+        ));
+        // nothing was distributed, the admin did not receive anything
+        assert_eq!(
+            QuadraticFunding::rounds(round_id)
+                .unwrap()
+                .undistributed_pool,
+            3 * net_amount
+        );
+        assert_eq!(Balances::free_balance(5), 0);
+
+        let balance_1 = Balances::free_balance(1);
+        let balance_2 = Balances::free_balance(2);
+        // anyone can trigger the refund
+        assert_ok!(QuadraticFunding::refund_sponsor(
+            Origin::signed(3),
+            round_id,
+            1
+        ));
+        assert_ok!(QuadraticFunding::refund_sponsor(
+            Origin::signed(3),
+            round_id,
+            2
+        ));
+        assert_eq!(Balances::free_balance(1), balance_1 + net_amount);
+        assert_eq!(Balances::free_balance(2), balance_2 + 2 * net_amount);
+        assert_eq!(
+            Balances::free_balance(QuadraticFunding::round_account(round_id)),
+            0
+        );
+        assert_noop!(
+            QuadraticFunding::refund_sponsor(Origin::signed(3), round_id, 1),
+            Error::<Runtime>::AlreadyRefunded
+        );
+        assert_noop!(
+            QuadraticFunding::refund_sponsor(Origin::signed(3), round_id, 3),
+            Error::<Runtime>::SponsorshipNotExist
+        );
+    })
+}
+
+#[test]
+fn test_refund_sponsors_pro_rata() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let donate_amount = 1_000_000_000_000_000;
+        let net_amount = donate_amount - 6u128.checked_mul(donate_amount / 1000).unwrap();
+        start_sponsored_round(round_id);
+        // only half of the pool can be distributed to the single project
+        assert_ok!(QuadraticFunding::set_max_match_per_project(
+            Origin::root(),
+            round_id,
+            Perbill::from_percent(50)
+        ));
+        assert_ok!(QuadraticFunding::donate(
+            Origin::signed(1),
+            round_id,
+            donate_amount,
+            CurrencyId::DORA,
+        ));
+        assert_ok!(QuadraticFunding::donate(
+            Origin::signed(2),
+            round_id,
+            2 * donate_amount,
+            CurrencyId::DORA,
+        ));
+        let project_hash = BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 0u128));
+        assert_ok!(QuadraticFunding::register_project(
+            Origin::signed(1),
+            round_id,
+            project_hash,
+            "project".to_string().into()
+        ));
+        for who in [2, 3] {
+            assert_ok!(QuadraticFunding::vote(
+                Origin::signed(who),
+                CurrencyId::DORA,
+                round_id,
+                project_hash,
+                1
+            ));
+        }
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            round_id,
+            QuadraticFunding::round_witness(round_id)
+        ));
+        assert_eq!(
+            QuadraticFunding::projects(round_id, project_hash)
+                .unwrap()
+                .support_fund,
+            3 * net_amount / 2
+        );
+        assert_eq!(
+            QuadraticFunding::rounds(round_id)
+                .unwrap()
+                .undistributed_pool,
+            3 * net_amount / 2
+        );
+
+        let balance_1 = Balances::free_balance(1);
+        let balance_2 = Balances::free_balance(2);
+        assert_ok!(QuadraticFunding::refund_sponsor(
+            Origin::signed(1),
+            round_id,
+            1
+        ));
+        assert_ok!(QuadraticFunding::refund_sponsor(
+            Origin::signed(2),
+            round_id,
+            2
+        ));
+        assert_eq!(Balances::free_balance(1), balance_1 + net_amount / 2);
+        assert_eq!(Balances::free_balance(2), balance_2 + net_amount);
+    })
+}
+
+#[test]
+fn test_sponsor_conditions_not_met_are_refunded() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let donate_amount = 1_000_000_000_000_000;
+        let net_amount = donate_amount - 6u128.checked_mul(donate_amount / 1000).unwrap();
+        start_sponsored_round(round_id);
+        assert_noop!(
+            QuadraticFunding::sponsor(
+                Origin::signed(1),
+                round_id,
+                donate_amount,
+                CurrencyId::DORA,
+                SponsorConditions {
+                    min_projects: 0,
+                    expiry: Some(0),
+                }
+            ),
+            Error::<Runtime>::InvalidSponsorConditions
+        );
+        // sponsor 1 only funds rounds with at least two projects
+        assert_ok!(QuadraticFunding::sponsor(
+            Origin::signed(1),
+            round_id,
+            donate_amount,
+            CurrencyId::DORA,
+            SponsorConditions {
+                min_projects: 2,
+                expiry: None,
+            }
+        ));
+        assert_noop!(
+            QuadraticFunding::donate(Origin::signed(1), round_id, donate_amount, CurrencyId::DORA,),
+            Error::<Runtime>::ConflictingSponsorConditions
+        );
+        assert_ok!(QuadraticFunding::donate(
+            Origin::signed(2),
+            round_id,
+            donate_amount,
+            CurrencyId::DORA,
+        ));
+        let project_hash = BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 0u128));
+        assert_ok!(QuadraticFunding::register_project(
+            Origin::signed(1),
+            round_id,
+            project_hash,
+            "project".to_string().into()
+        ));
+        for who in [2, 3] {
+            assert_ok!(QuadraticFunding::vote(
+                Origin::signed(who),
+                CurrencyId::DORA,
+                round_id,
+                project_hash,
+                1
+            ));
+        }
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            round_id,
+            QuadraticFunding::round_witness(round_id)
+        ));
+        assert!(QuadraticFunding::sponsorships(round_id, 1).unwrap().void);
+        // only the donation of sponsor 2 was matched
+        assert_eq!(
+            QuadraticFunding::projects(round_id, project_hash)
+                .unwrap()
+                .support_fund,
+            net_amount
+        );
+
+        let balance_1 = Balances::free_balance(1);
+        let balance_2 = Balances::free_balance(2);
+        assert_ok!(QuadraticFunding::refund_sponsor(
+            Origin::signed(1),
+            round_id,
+            1
+        ));
+        assert_ok!(QuadraticFunding::refund_sponsor(
+            Origin::signed(2),
+            round_id,
+            2
+        ));
+        assert_eq!(Balances::free_balance(1), balance_1 + net_amount);
+        assert_eq!(Balances::free_balance(2), balance_2);
+    })
+}
+
+#[test]
+fn test_expired_sponsorship_leaves_ongoing_round() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let donate_amount = 1_000_000_000_000_000;
+        let net_amount = donate_amount - 6u128.checked_mul(donate_amount / 1000).unwrap();
+        start_sponsored_round(round_id);
+        assert_ok!(QuadraticFunding::sponsor(
+            Origin::signed(1),
+            round_id,
+            donate_amount,
+            CurrencyId::DORA,
+            SponsorConditions {
+                min_projects: 0,
+                expiry: Some(10),
+            }
+        ));
+        assert_ok!(QuadraticFunding::donate(
+            Origin::signed(2),
+            round_id,
+            donate_amount,
+            CurrencyId::DORA,
+        ));
+        System::set_block_number(10);
+        assert_noop!(
+            QuadraticFunding::refund_sponsor(Origin::signed(1), round_id, 1),
+            Error::<Runtime>::RoundIsOngoing
+        );
+
+        System::set_block_number(11);
+        let balance_1 = Balances::free_balance(1);
+        assert_ok!(QuadraticFunding::refund_sponsor(
+            Origin::signed(1),
+            round_id,
+            1
+        ));
+        assert_eq!(Balances::free_balance(1), balance_1 + net_amount);
+        assert_eq!(
+            QuadraticFunding::rounds(round_id).unwrap().support_pool,
+            net_amount
+        );
+        // the refunded sponsor does not count anymore when the round ends
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            round_id,
+            QuadraticFunding::round_witness(round_id)
+        ));
+        assert_eq!(
+            QuadraticFunding::rounds(round_id)
+                .unwrap()
+                .undistributed_pool,
+            net_amount
+        );
+    })
+}
+
+#[test]
+fn test_fund_round_from_treasury() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let fund_amount = 1_000_000_000_000_000;
+        let treasury = TreasuryAccount::get();
+        start_sponsored_round(round_id);
+        assert_noop!(
+            QuadraticFunding::fund_round_from_treasury(Origin::signed(1), round_id, fund_amount),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            QuadraticFunding::fund_round_from_treasury(Origin::root(), 2, fund_amount),
+            Error::<Runtime>::RoundNotExist
+        );
+        assert_ok!(QuadraticFunding::fund_round_from_treasury(
+            Origin::root(),
+            round_id,
+            fund_amount
+        ));
+        // treasury funds are not charged with fees
+        assert_eq!(
+            Balances::free_balance(treasury),
+            100 * DOLLARS - fund_amount
+        );
+        assert_eq!(
+            Balances::free_balance(QuadraticFunding::round_account(round_id)),
+            fund_amount
+        );
+        let round = QuadraticFunding::rounds(round_id).unwrap();
+        assert_eq!(round.support_pool, fund_amount);
+        assert_eq!(round.total_tax, 0);
+        assert_eq!(round.sponsors, 1);
+        assert_eq!(
+            QuadraticFunding::sponsorships(round_id, treasury)
+                .unwrap()
+                .amount,
+            fund_amount
+        );
+
+        // nothing is distributed, the treasury gets its funds back
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            round_id,
+            QuadraticFunding::round_witness(round_id)
+        ));
+        assert_ok!(QuadraticFunding::refund_sponsor(
+            Origin::signed(1),
+            round_id,
+            treasury
+        ));
+        assert_eq!(Balances::free_balance(treasury), 100 * DOLLARS);
+        assert_noop!(
+            QuadraticFunding::fund_round_from_treasury(Origin::root(), round_id, fund_amount),
+            Error::<Runtime>::RoundHasEnded
+        );
+    })
+}
+
+#[test]
+fn test_sponsor_slots() {
+    new_test_ext().execute_with(|| {
+        MaxSponsors::set(1);
+        let round_id = 1;
+        start_sponsored_round(round_id);
+        assert_noop!(
+            QuadraticFunding::donate(Origin::signed(1), round_id, DOLLARS / 2, CurrencyId::DORA),
+            Error::<Runtime>::SponsorshipTooSmall
+        );
+        assert_ok!(QuadraticFunding::donate(
+            Origin::signed(1),
+            round_id,
+            DOLLARS,
+            CurrencyId::DORA
+        ));
+        // the sponsors can add less than the minimum
+        assert_ok!(QuadraticFunding::donate(
+            Origin::signed(1),
+            round_id,
+            DOLLARS / 2,
+            CurrencyId::DORA
+        ));
+        assert_noop!(
+            QuadraticFunding::donate(Origin::signed(2), round_id, DOLLARS, CurrencyId::DORA),
+            Error::<Runtime>::TooManySponsors
+        );
+        // the treasury is not counted against the sponsors
+        assert_ok!(QuadraticFunding::fund_round_from_treasury(
+            Origin::root(),
+            round_id,
+            DOLLARS / 2
+        ));
+        assert_eq!(QuadraticFunding::rounds(round_id).unwrap().sponsors, 2);
+    })
+}
+
+#[test]
+fn test_sweep_fees_to_treasury() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let donate_amount = 1_000_000_000_000_000;
+        let fee = 6u128.checked_mul(donate_amount / 1000).unwrap();
+        let treasury = TreasuryAccount::get();
+        start_sponsored_round(round_id);
+        assert_noop!(
+            QuadraticFunding::sweep_fees(Origin::signed(1), CurrencyId::DORA),
+            Error::<Runtime>::NoFeesToSweep
+        );
+        assert_ok!(QuadraticFunding::donate(
+            Origin::signed(1),
+            round_id,
+            donate_amount,
+            CurrencyId::DORA,
+        ));
+        assert_eq!(Balances::free_balance(QuadraticFunding::account_id()), fee);
+        assert_eq!(
+            QuadraticFunding::fee_destination(),
+            FeeDestination::Treasury
+        );
+        assert_ok!(QuadraticFunding::sweep_fees(
+            Origin::signed(2),
+            CurrencyId::DORA
+        ));
+        assert_eq!(Balances::free_balance(QuadraticFunding::account_id()), 0);
+        assert_eq!(Balances::free_balance(treasury), 100 * DOLLARS + fee);
+        System::assert_last_event(Event::QuadraticFunding(crate::Event::<Runtime>::FeesSwept(
+            CurrencyId::DORA,
+            fee,
+            FeeDestination::Treasury,
+        )));
+        assert_noop!(
+            QuadraticFunding::sweep_fees(Origin::signed(2), CurrencyId::DORA),
+            Error::<Runtime>::NoFeesToSweep
+        );
+    })
+}
+
+#[test]
+fn test_sweep_fees_burn() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let donate_amount = 1_000_000_000_000_000;
+        let fee = 6u128.checked_mul(donate_amount / 1000).unwrap();
+        start_sponsored_round(round_id);
+        assert_noop!(
+            QuadraticFunding::set_fee_destination(Origin::signed(1), FeeDestination::Burn),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(QuadraticFunding::set_fee_destination(
+            Origin::root(),
+            FeeDestination::Burn
+        ));
+        assert_ok!(QuadraticFunding::donate(
+            Origin::signed(1),
+            round_id,
+            donate_amount,
+            CurrencyId::DORA,
+        ));
+        let issuance = Balances::total_issuance();
+        assert_ok!(QuadraticFunding::sweep_fees(
+            Origin::signed(2),
+            CurrencyId::DORA
+        ));
+        assert_eq!(Balances::free_balance(QuadraticFunding::account_id()), 0);
+        assert_eq!(Balances::total_issuance(), issuance - fee);
+    })
+}
+
+#[test]
+fn test_sweep_fees_to_next_round() {
+    new_test_ext().execute_with(|| {
+        let donate_amount = 1_000_000_000_000_000;
+        let fee = 6u128.checked_mul(donate_amount / 1000).unwrap();
+        let pallet_account = QuadraticFunding::account_id();
+        start_sponsored_round(1);
+        assert_ok!(QuadraticFunding::donate(
+            Origin::signed(1),
+            1,
+            donate_amount,
+            CurrencyId::DORA,
+        ));
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            1,
+            QuadraticFunding::round_witness(1)
+        ));
+        assert_ok!(QuadraticFunding::set_fee_destination(
+            Origin::root(),
+            FeeDestination::Round(2)
+        ));
+        // the next round is not started yet
+        assert_noop!(
+            QuadraticFunding::sweep_fees(Origin::signed(2), CurrencyId::DORA),
+            Error::<Runtime>::RoundNotExist
+        );
+        start_sponsored_round(2);
+        assert_ok!(QuadraticFunding::sweep_fees(
+            Origin::signed(2),
+            CurrencyId::DORA
+        ));
+        // the fees are added to the pool without being charged again
+        let round = QuadraticFunding::rounds(2).unwrap();
+        assert_eq!(round.support_pool, fee);
+        assert_eq!(round.total_tax, 0);
+        assert_eq!(
+            QuadraticFunding::sponsorships(2, pallet_account)
+                .unwrap()
+                .amount,
+            fee
+        );
+        assert_eq!(Balances::free_balance(pallet_account), 0);
+        assert_eq!(
+            Balances::free_balance(QuadraticFunding::round_account(2)),
+            fee
+        );
+    })
+}
+
+fn start_milestone_round(round_id: u32) -> sp_core::H256 {
+    let donate_amount = 1_000_000_000_000_000;
+    start_sponsored_round(round_id);
+    assert_ok!(QuadraticFunding::donate(
+        Origin::signed(1),
+        round_id,
+        donate_amount,
+        CurrencyId::DORA,
+    ));
+    let project_hash = BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 0u128));
+    assert_ok!(QuadraticFunding::register_project(
+        Origin::signed(2),
+        round_id,
+        project_hash,
+        "project".to_string().into()
+    ));
+    project_hash
+}
+
+#[test]
+fn test_set_milestones() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let project_hash = start_milestone_round(round_id);
+        assert_noop!(
+            QuadraticFunding::set_milestones(
+                Origin::signed(1),
+                round_id,
+                project_hash,
+                vec![Perbill::one()]
+            ),
+            Error::<Runtime>::NotProjectOwner
+        );
+        // shares must add up to 100%
+        assert_noop!(
+            QuadraticFunding::set_milestones(
+                Origin::signed(2),
+                round_id,
+                project_hash,
+                vec![Perbill::from_percent(30), Perbill::from_percent(60)]
+            ),
+            Error::<Runtime>::InvalidMilestones
+        );
+        assert_noop!(
+            QuadraticFunding::set_milestones(
+                Origin::signed(2),
+                round_id,
+                project_hash,
+                vec![Perbill::zero(), Perbill::one()]
+            ),
+            Error::<Runtime>::InvalidMilestones
+        );
+        // too many milestones
+        assert_noop!(
+            QuadraticFunding::set_milestones(
+                Origin::signed(2),
+                round_id,
+                project_hash,
+                vec![Perbill::from_percent(10); 10]
+            ),
+            Error::<Runtime>::InvalidMilestones
+        );
+        assert_ok!(QuadraticFunding::set_milestones(
+            Origin::signed(2),
+            round_id,
+            project_hash,
+            vec![Perbill::from_percent(30), Perbill::from_percent(70)]
+        ));
+        assert_eq!(
+            QuadraticFunding::milestones(round_id, project_hash)
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_ok!(QuadraticFunding::vote(
+            Origin::signed(3),
+            CurrencyId::DORA,
+            round_id,
+            project_hash,
+            1
+        ));
+        // votes are escrowed by the round account
+        assert_eq!(Balances::free_balance(5), 0);
+        assert_noop!(
+            QuadraticFunding::set_milestones(
+                Origin::signed(2),
+                round_id,
+                project_hash,
+                vec![Perbill::one()]
+            ),
+            Error::<Runtime>::ProjectAlreadyVoted
+        );
+    })
+}
+
+#[test]
+fn test_approve_milestones() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let project_hash = start_milestone_round(round_id);
+        assert_ok!(QuadraticFunding::set_milestones(
+            Origin::signed(2),
+            round_id,
+            project_hash,
+            vec![Perbill::from_percent(30), Perbill::from_percent(70)]
+        ));
+        for who in [1, 3] {
+            assert_ok!(QuadraticFunding::vote(
+                Origin::signed(who),
+                CurrencyId::DORA,
+                round_id,
+                project_hash,
+                1
+            ));
+        }
+        assert_noop!(
+            QuadraticFunding::approve_milestone(Origin::root(), round_id, project_hash, 0),
+            Error::<Runtime>::EscrowNotExist
+        );
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            round_id,
+            QuadraticFunding::round_witness(round_id)
+        ));
+        let project = QuadraticFunding::projects(round_id, project_hash).unwrap();
+        let total = project.grants + project.support_fund;
+        let escrow = QuadraticFunding::escrows(round_id, project_hash).unwrap();
+        assert_eq!(escrow.total, total);
+        assert_eq!(escrow.deadline, 1 + MilestoneDeadline::get());
+        // the admin did not receive the funds of the project
+        assert_eq!(Balances::free_balance(5), 0);
+        assert_eq!(
+            Balances::free_balance(QuadraticFunding::round_account(round_id)),
+            total
+        );
+
+        assert_noop!(
+            QuadraticFunding::approve_milestone(Origin::signed(3), round_id, project_hash, 0),
+            DispatchError::BadOrigin
+        );
+        // the round admin approves the first milestone
+        assert_ok!(QuadraticFunding::approve_milestone(
+            Origin::signed(5),
+            round_id,
+            project_hash,
+            0
+        ));
+        let first_tranche = Perbill::from_percent(30) * total;
+        assert_eq!(Balances::free_balance(2), 100 * DOLLARS + first_tranche);
+        assert_noop!(
+            QuadraticFunding::approve_milestone(Origin::signed(5), round_id, project_hash, 0),
+            Error::<Runtime>::MilestoneAlreadyReleased
+        );
+        assert_noop!(
+            QuadraticFunding::approve_milestone(Origin::signed(5), round_id, project_hash, 2),
+            Error::<Runtime>::MilestoneNotExist
+        );
+        assert_noop!(
+            QuadraticFunding::reclaim_escrow(Origin::signed(3), round_id, project_hash),
+            Error::<Runtime>::EscrowNotExpired
+        );
+        // the reviewer origin approves the last one
+        assert_ok!(QuadraticFunding::approve_milestone(
+            Origin::root(),
+            round_id,
+            project_hash,
+            1
+        ));
+        assert_eq!(Balances::free_balance(2), 100 * DOLLARS + total);
+        assert_eq!(
+            Balances::free_balance(QuadraticFunding::round_account(round_id)),
+            0
+        );
+    })
+}
+
+#[test]
+fn test_reclaim_expired_escrow() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let treasury = TreasuryAccount::get();
+        let project_hash = start_milestone_round(round_id);
+        assert_ok!(QuadraticFunding::set_milestones(
+            Origin::signed(2),
+            round_id,
+            project_hash,
+            vec![Perbill::from_percent(50), Perbill::from_percent(50)]
+        ));
+        for who in [1, 3] {
+            assert_ok!(QuadraticFunding::vote(
+                Origin::signed(who),
+                CurrencyId::DORA,
+                round_id,
+                project_hash,
+                1
+            ));
+        }
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            round_id,
+            QuadraticFunding::round_witness(round_id)
+        ));
+        let total = QuadraticFunding::escrows(round_id, project_hash)
+            .unwrap()
+            .total;
+        assert_ok!(QuadraticFunding::approve_milestone(
+            Origin::root(),
+            round_id,
+            project_hash,
+            0
+        ));
+        let released = Perbill::from_percent(50) * total;
+
+        System::set_block_number(2 + MilestoneDeadline::get());
+        assert_noop!(
+            QuadraticFunding::approve_milestone(Origin::root(), round_id, project_hash, 1),
+            Error::<Runtime>::EscrowExpired
+        );
+        assert_ok!(QuadraticFunding::reclaim_escrow(
+            Origin::signed(3),
+            round_id,
+            project_hash
+        ));
+        assert_eq!(
+            Balances::free_balance(treasury),
+            100 * DOLLARS + total - released
+        );
+        assert!(QuadraticFunding::escrows(round_id, project_hash).is_none());
+        assert_noop!(
+            QuadraticFunding::reclaim_escrow(Origin::signed(3), round_id, project_hash),
+            Error::<Runtime>::EscrowNotExist
+        );
+    })
+}
+
+#[test]
+fn test_set_vesting_schedule() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let project_hash = start_milestone_round(round_id);
+        let schedule = VestingSchedule {
+            start: 10,
+            duration: 100,
+            cliff: 20,
+        };
+        assert_noop!(
+            QuadraticFunding::set_vesting_schedule(Origin::signed(1), round_id, schedule.clone()),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            QuadraticFunding::set_vesting_schedule(
+                Origin::root(),
+                round_id,
+                VestingSchedule {
+                    start: 10,
+                    duration: 10,
+                    cliff: 20,
+                }
+            ),
+            Error::<Runtime>::InvalidVestingSchedule
+        );
+        assert_ok!(QuadraticFunding::set_milestones(
+            Origin::signed(2),
+            round_id,
+            project_hash,
+            vec![Perbill::one()]
+        ));
+        // a round pays its projects either by milestones or by vesting
+        assert_noop!(
+            QuadraticFunding::set_vesting_schedule(Origin::root(), round_id, schedule.clone()),
+            Error::<Runtime>::ConflictingPayoutSchedule
+        );
+
+        let round_id = 2;
+        let project_hash = start_milestone_round(round_id);
+        assert_ok!(QuadraticFunding::set_vesting_schedule(
+            Origin::root(),
+            round_id,
+            schedule.clone()
+        ));
+        assert_eq!(
+            QuadraticFunding::vesting_schedule(round_id),
+            Some(schedule.clone())
+        );
+        assert_noop!(
+            QuadraticFunding::set_milestones(
+                Origin::signed(2),
+                round_id,
+                project_hash,
+                vec![Perbill::one()]
+            ),
+            Error::<Runtime>::ConflictingPayoutSchedule
+        );
+
+        let round_id = 3;
+        let project_hash = start_milestone_round(round_id);
+        assert_ok!(QuadraticFunding::vote(
+            Origin::signed(3),
+            CurrencyId::DORA,
+            round_id,
+            project_hash,
+            1
+        ));
+        // the votes were already paid to the admin
+        assert_noop!(
+            QuadraticFunding::set_vesting_schedule(Origin::root(), round_id, schedule),
+            Error::<Runtime>::ProjectAlreadyVoted
+        );
+    })
+}
+
+#[test]
+fn test_claim_and_revoke_vested_grant() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let treasury = TreasuryAccount::get();
+        let project_hash = start_milestone_round(round_id);
+        assert_ok!(QuadraticFunding::set_vesting_schedule(
+            Origin::root(),
+            round_id,
+            VestingSchedule {
+                start: 10,
+                duration: 100,
+                cliff: 20,
+            }
+        ));
+        for who in [1, 3] {
+            assert_ok!(QuadraticFunding::vote(
+                Origin::signed(who),
+                CurrencyId::DORA,
+                round_id,
+                project_hash,
+                1
+            ));
+        }
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            round_id,
+            QuadraticFunding::round_witness(round_id)
+        ));
+        let project = QuadraticFunding::projects(round_id, project_hash).unwrap();
+        let total = project.grants + project.support_fund;
+        assert_eq!(
+            QuadraticFunding::vested_grants(round_id, project_hash)
+                .unwrap()
+                .total,
+            total
+        );
+        // the admin did not receive the funds of the project
+        assert_eq!(Balances::free_balance(5), 0);
+
+        // still in the cliff
+        System::set_block_number(29);
+        assert_noop!(
+            QuadraticFunding::claim_vested(Origin::signed(2), round_id, project_hash),
+            Error::<Runtime>::NothingToClaim
+        );
+        System::set_block_number(40);
+        assert_noop!(
+            QuadraticFunding::claim_vested(Origin::signed(3), round_id, project_hash),
+            Error::<Runtime>::NotProjectOwner
+        );
+        assert_ok!(QuadraticFunding::claim_vested(
+            Origin::signed(2),
+            round_id,
+            project_hash
+        ));
+        assert_eq!(Balances::free_balance(2), 100 * DOLLARS + total * 30 / 100);
+
+        // the round admin revokes the abandoned project
+        System::set_block_number(60);
+        assert_noop!(
+            QuadraticFunding::revoke_vesting(Origin::signed(3), round_id, project_hash),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(QuadraticFunding::revoke_vesting(
+            Origin::signed(5),
+            round_id,
+            project_hash
+        ));
+        assert_eq!(
+            Balances::free_balance(treasury),
+            100 * DOLLARS + total - total * 50 / 100
+        );
+        assert_noop!(
+            QuadraticFunding::revoke_vesting(Origin::root(), round_id, project_hash),
+            Error::<Runtime>::VestingAlreadyRevoked
+        );
+
+        // what was vested before the revocation can still be claimed
+        System::set_block_number(200);
+        assert_ok!(QuadraticFunding::claim_vested(
+            Origin::signed(2),
+            round_id,
+            project_hash
+        ));
+        assert_eq!(Balances::free_balance(2), 100 * DOLLARS + total * 50 / 100);
+        assert_noop!(
+            QuadraticFunding::claim_vested(Origin::signed(2), round_id, project_hash),
+            Error::<Runtime>::NothingToClaim
+        );
+        assert_eq!(
+            Balances::free_balance(QuadraticFunding::round_account(round_id)),
+            0
+        );
+    })
+}
+
+/// A round with a challenge period of 10 blocks and two projects sharing the matching
+fn end_challenged_round(round_id: u32) -> (sp_core::H256, sp_core::H256) {
+    let donate_amount = 1_000_000_000_000_000;
+    start_sponsored_round(round_id);
+    // projects can not be flagged once the round can be archived
+    assert_noop!(
+        QuadraticFunding::set_challenge_period(Origin::root(), round_id, ArchiveDelay::get() + 1),
+        Error::<Runtime>::InvalidChallengePeriod
+    );
+    assert_ok!(QuadraticFunding::set_challenge_period(
+        Origin::root(),
+        round_id,
+        10
+    ));
+    assert_ok!(QuadraticFunding::donate(
+        Origin::signed(1),
+        round_id,
+        donate_amount,
+        CurrencyId::DORA,
+    ));
+    let project_a = BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 0u128));
+    let project_b = BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 1u128));
+    for project_hash in [project_a, project_b] {
+        assert_ok!(QuadraticFunding::register_project(
+            Origin::signed(2),
+            round_id,
+            project_hash,
+            "project".to_string().into()
+        ));
+        for who in [1, 3] {
+            assert_ok!(QuadraticFunding::vote(
+                Origin::signed(who),
+                CurrencyId::DORA,
+                round_id,
+                project_hash,
+                1
+            ));
+        }
+    }
+    assert_ok!(QuadraticFunding::end_round(
+        Origin::root(),
+        round_id,
+        QuadraticFunding::round_witness(round_id)
+    ));
+    (project_a, project_b)
+}
+
+#[test]
+fn test_flagged_matching_is_redistributed() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let net_amount = 1_000_000_000_000_000 - 6_000_000_000_000;
+        assert_noop!(
+            QuadraticFunding::flag_project(
+                Origin::signed(1),
+                round_id,
+                BlakeTwo256::hash(b"project"),
+                BlakeTwo256::hash(b"evidence")
+            ),
+            Error::<Runtime>::NotInChallengePeriod
+        );
+        let (project_a, project_b) = end_challenged_round(round_id);
+        assert_eq!(QuadraticFunding::settlement_deadline(round_id), Some(11));
+        // the projects are only ranked once their funds are final
+        assert_noop!(
+            QuadraticFunding::claim_project_badge(Origin::signed(2), round_id, project_b),
+            Error::<Runtime>::RoundNotSettled
+        );
+        // the matching is not paid during the challenge period
+        assert_eq!(
+            Balances::free_balance(QuadraticFunding::round_account(round_id)),
+            net_amount
+        );
+        assert_noop!(
+            QuadraticFunding::refund_sponsor(Origin::signed(1), round_id, 1),
+            Error::<Runtime>::RoundNotSettled
+        );
+        assert_noop!(
+            QuadraticFunding::finalize_round(Origin::signed(1), round_id, 2),
+            Error::<Runtime>::ChallengePeriodNotOver
+        );
+
+        let evidence = BlakeTwo256::hash(b"evidence");
+        assert_noop!(
+            QuadraticFunding::flag_project(Origin::signed(4), round_id, project_a, evidence),
+            Error::<Runtime>::InsufficientBond
+        );
+        assert_ok!(QuadraticFunding::flag_project(
+            Origin::signed(3),
+            round_id,
+            project_a,
+            evidence
+        ));
+        assert_eq!(Balances::reserved_balance(3), ChallengeBond::get());
+        assert_eq!(
+            Balances::reserved_balance_named(&RESERVE_ID, &3),
+            ChallengeBond::get()
+        );
+        assert_noop!(
+            QuadraticFunding::flag_project(Origin::root(), round_id, project_a, evidence),
+            Error::<Runtime>::ProjectAlreadyFlagged
+        );
+
+        System::set_block_number(12);
+        assert_noop!(
+            QuadraticFunding::flag_project(Origin::root(), round_id, project_b, evidence),
+            Error::<Runtime>::NotInChallengePeriod
+        );
+        assert_noop!(
+            QuadraticFunding::finalize_round(Origin::signed(1), round_id, 2),
+            Error::<Runtime>::UnresolvedFlags
+        );
+        assert_noop!(
+            QuadraticFunding::resolve_flag(
+                Origin::signed(1),
+                round_id,
+                project_a,
+                FlagResolution::Redistribute
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(QuadraticFunding::resolve_flag(
+            Origin::root(),
+            round_id,
+            project_a,
+            FlagResolution::Redistribute
+        ));
+        // the flag was upheld, the challenger gets the bond back
+        assert_eq!(Balances::reserved_balance(3), 0);
+        assert_eq!(
+            QuadraticFunding::projects(round_id, project_a)
+                .unwrap()
+                .support_fund,
+            0
+        );
+        assert_eq!(
+            QuadraticFunding::projects(round_id, project_b)
+                .unwrap()
+                .support_fund,
+            net_amount
+        );
+
+        let admin_balance_before = Balances::free_balance(5);
+        // the results are final once the round is finalized
+        assert!(EndedRounds::get().is_empty());
+        // the projects witnessed can not be less than those of the round
+        assert_noop!(
+            QuadraticFunding::finalize_round(Origin::signed(1), round_id, 1),
+            Error::<Runtime>::InvalidWitness
+        );
+        assert_ok!(QuadraticFunding::finalize_round(
+            Origin::signed(1),
+            round_id,
+            2
+        ));
+        let ended = EndedRounds::get();
+        assert_eq!(ended.len(), 1);
+        assert!(ended[0]
+            .1
+            .iter()
+            .any(|result| result.hash == project_b && result.support_fund == net_amount));
+        assert_eq!(QuadraticFunding::project_rank(round_id, project_b), Some(1));
+        assert_eq!(QuadraticFunding::project_rank(round_id, project_a), Some(2));
+        assert!(QuadraticFunding::settlement_deadline(round_id).is_none());
+        assert_eq!(Balances::free_balance(5), admin_balance_before + net_amount);
+        assert_noop!(
+            QuadraticFunding::finalize_round(Origin::signed(1), round_id, 2),
+            Error::<Runtime>::NotInChallengePeriod
+        );
+        assert_ok!(QuadraticFunding::refund_sponsor(
+            Origin::signed(1),
+            round_id,
+            1
+        ));
+    })
+}
+
+#[test]
+fn test_rejected_flag_restores_matching() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let treasury = TreasuryAccount::get();
+        let evidence = BlakeTwo256::hash(b"evidence");
+        let (project_a, project_b) = end_challenged_round(round_id);
+        let support_fund = QuadraticFunding::projects(round_id, project_a)
+            .unwrap()
+            .support_fund;
+        // the challenge origin does not need a bond
+        assert_ok!(QuadraticFunding::flag_project(
+            Origin::root(),
+            round_id,
+            project_a,
+            evidence
+        ));
+        assert_ok!(QuadraticFunding::flag_project(
+            Origin::signed(3),
+            round_id,
+            project_b,
+            evidence
+        ));
+        assert_ok!(QuadraticFunding::resolve_flag(
+            Origin::root(),
+            round_id,
+            project_a,
+            FlagResolution::Restore
+        ));
+        assert_ok!(QuadraticFunding::resolve_flag(
+            Origin::root(),
+            round_id,
+            project_b,
+            FlagResolution::Restore
+        ));
+        // the bond of the rejected flag goes to the treasury
+        assert_eq!(Balances::reserved_balance(3), 0);
+        assert_eq!(
+            Balances::free_balance(treasury),
+            100 * DOLLARS + ChallengeBond::get()
+        );
+        for project_hash in [project_a, project_b] {
+            assert_eq!(
+                QuadraticFunding::projects(round_id, project_hash)
+                    .unwrap()
+                    .support_fund,
+                support_fund
+            );
+        }
+        assert_noop!(
+            QuadraticFunding::resolve_flag(
+                Origin::root(),
+                round_id,
+                project_a,
+                FlagResolution::Restore
+            ),
+            Error::<Runtime>::FlagNotExist
+        );
+    })
+}
+
+#[test]
+fn test_conviction_boosts_matching() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let net_amount = 1_000_000_000_000_000 - 6_000_000_000_000;
+        let lock_id = QuadraticFunding::conviction_lock_id(round_id);
+        start_sponsored_round(round_id);
+        assert_ok!(QuadraticFunding::donate(
+            Origin::signed(2),
+            round_id,
+            1_000_000_000_000_000,
+            CurrencyId::DORA,
+        ));
+        let project_a = BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 0u128));
+        let project_b = BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 1u128));
+        for project_hash in [project_a, project_b] {
+            assert_ok!(QuadraticFunding::register_project(
+                Origin::signed(2),
+                round_id,
+                project_hash,
+                "project".to_string().into()
+            ));
+        }
+        assert_noop!(
+            QuadraticFunding::vote_with_conviction(
+                Origin::signed(1),
+                CurrencyId::DORA,
+                round_id,
+                project_a,
+                1,
+                Conviction::Locked3x
+            ),
+            Error::<Runtime>::ConvictionNotEnabled
+        );
+        assert_ok!(QuadraticFunding::set_conviction_period(
+            Origin::root(),
+            round_id,
+            10
+        ));
+        assert_ok!(QuadraticFunding::vote_with_conviction(
+            Origin::signed(1),
+            CurrencyId::DORA,
+            round_id,
+            project_a,
+            1,
+            Conviction::Locked3x
+        ));
+        // as much as was paid stays locked
+        let lock = QuadraticFunding::conviction_locks(round_id, 1).unwrap();
+        assert_eq!(lock.amount, 1_000_000_000_000);
+        assert_eq!(lock.conviction, Conviction::Locked3x);
+        assert!(Balances::locks(1)
+            .iter()
+            .any(|l| l.id == lock_id && l.amount == 1_000_000_000_000));
+        for (who, project_hash) in [(3, project_a), (1, project_b), (3, project_b)] {
+            assert_ok!(QuadraticFunding::vote(
+                Origin::signed(who),
+                CurrencyId::DORA,
+                round_id,
+                project_hash,
+                1
+            ));
+        }
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            round_id,
+            QuadraticFunding::round_witness(round_id)
+        ));
+        // project a: 3 * 1 boosted cross products, project b: 1 * 1
+        assert_eq!(
+            QuadraticFunding::projects(round_id, project_a)
+                .unwrap()
+                .support_fund,
+            net_amount * 3 / 4
+        );
+        assert_eq!(
+            QuadraticFunding::projects(round_id, project_b)
+                .unwrap()
+                .support_fund,
+            net_amount / 4
+        );
+
+        // locked for 2 conviction periods after the end of the round
+        System::set_block_number(20);
+        assert_noop!(
+            QuadraticFunding::unlock_conviction(Origin::signed(3), round_id, 1),
+            Error::<Runtime>::ConvictionLockNotExpired
+        );
+        System::set_block_number(21);
+        assert_ok!(QuadraticFunding::unlock_conviction(
+            Origin::signed(3),
+            round_id,
+            1
+        ));
+        assert!(Balances::locks(1).is_empty());
+        assert_noop!(
+            QuadraticFunding::unlock_conviction(Origin::signed(3), round_id, 1),
+            Error::<Runtime>::ConvictionLockNotExist
+        );
+    })
+}
+
+#[test]
+fn test_conviction_needs_balance_to_lock() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let project_hash = start_milestone_round(round_id);
+        assert_ok!(QuadraticFunding::set_conviction_period(
+            Origin::root(),
+            round_id,
+            10
+        ));
+        // account 4 can pay the ballot but not lock as much afterwards
+        assert_noop!(
+            QuadraticFunding::vote_with_conviction(
+                Origin::signed(4),
+                CurrencyId::DORA,
+                round_id,
+                project_hash,
+                1,
+                Conviction::Locked2x
+            ),
+            Error::<Runtime>::InsufficientBalanceToLock
+        );
+        assert_ok!(QuadraticFunding::vote_with_conviction(
+            Origin::signed(4),
+            CurrencyId::DORA,
+            round_id,
+            project_hash,
+            1,
+            Conviction::None
+        ));
+    })
+}
+
+#[test]
+fn test_vote_as_delegate() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let project_hash = start_milestone_round(round_id);
+        let balance_of_3 = Balances::free_balance(3);
+        let balance_of_4 = Balances::free_balance(4);
+        assert_noop!(
+            QuadraticFunding::vote_as_delegate(
+                Origin::signed(4),
+                CurrencyId::DORA,
+                round_id,
+                project_hash,
+                1,
+                3
+            ),
+            Error::<Runtime>::DelegationNotExist
+        );
+        // 3 ballots in total cost 6 units of vote
+        assert_ok!(QuadraticFunding::delegate_votes(
+            Origin::signed(3),
+            round_id,
+            4,
+            6_000_000_000_000
+        ));
+        assert_noop!(
+            QuadraticFunding::vote_as_delegate(
+                Origin::signed(2),
+                CurrencyId::DORA,
+                round_id,
+                project_hash,
+                1,
+                3
+            ),
+            Error::<Runtime>::NotDelegate
+        );
+        assert_ok!(QuadraticFunding::vote_as_delegate(
+            Origin::signed(4),
+            CurrencyId::DORA,
+            round_id,
+            project_hash,
+            1,
+            3
+        ));
+        // the next 3 ballots would cost 9 units of vote
+        assert_noop!(
+            QuadraticFunding::vote_as_delegate(
+                Origin::signed(4),
+                CurrencyId::DORA,
+                round_id,
+                project_hash,
+                3,
+                3
+            ),
+            Error::<Runtime>::DelegationBudgetExceeded
+        );
+        assert_ok!(QuadraticFunding::vote_as_delegate(
+            Origin::signed(4),
+            CurrencyId::DORA,
+            round_id,
+            project_hash,
+            2,
+            3
+        ));
+        // the delegator pays and owns the ballots
+        assert_eq!(Balances::free_balance(3), balance_of_3 - 6_000_000_000_000);
+        assert_eq!(Balances::free_balance(4), balance_of_4);
+        assert_eq!(QuadraticFunding::vote_cost(3, round_id, project_hash, 1), 4);
+        assert_eq!(
+            QuadraticFunding::vote_delegations(round_id, 3)
+                .unwrap()
+                .spent,
+            6_000_000_000_000
+        );
+
+        assert_ok!(QuadraticFunding::undelegate_votes(
+            Origin::signed(3),
+            round_id
+        ));
+        assert_noop!(
+            QuadraticFunding::undelegate_votes(Origin::signed(3), round_id),
+            Error::<Runtime>::DelegationNotExist
+        );
+        assert_noop!(
+            QuadraticFunding::vote_as_delegate(
+                Origin::signed(4),
+                CurrencyId::DORA,
+                round_id,
+                project_hash,
+                1,
+                3
+            ),
+            Error::<Runtime>::DelegationNotExist
+        );
+    })
+}
+
+#[test]
+fn test_vote_signed() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let project_hash = start_milestone_round(round_id);
+        let balance_of_3 = Balances::free_balance(3);
+        let balance_of_4 = Balances::free_balance(4);
+        let payload = VotePayload {
+            currency_id: CurrencyId::DORA,
+            round_id,
+            project: project_hash,
+            ballot: 1,
+            nonce: 0,
+            expiry: 10,
+        };
+        assert_noop!(
+            QuadraticFunding::vote_signed(
+                Origin::signed(4),
+                payload.clone(),
+                TestSignature(3, QuadraticFunding::vote_signing_payload(&payload)),
+                1
+            ),
+            Error::<Runtime>::InvalidSignature
+        );
+        // the payload alone is not accepted
+        assert_noop!(
+            QuadraticFunding::vote_signed(
+                Origin::signed(4),
+                payload.clone(),
+                TestSignature(3, payload.encode()),
+                3
+            ),
+            Error::<Runtime>::InvalidSignature
+        );
+        // the relayer pays nothing for the ballots of the signer
+        assert_ok!(QuadraticFunding::vote_signed(
+            Origin::signed(4),
+            payload.clone(),
+            TestSignature(3, QuadraticFunding::vote_signing_payload(&payload)),
+            3
+        ));
+        assert_eq!(Balances::free_balance(3), balance_of_3 - 1_000_000_000_000);
+        assert_eq!(Balances::free_balance(4), balance_of_4);
+        assert_eq!(QuadraticFunding::vote_cost(3, round_id, project_hash, 1), 2);
+        assert_eq!(QuadraticFunding::vote_nonce(3), 1);
+        // nor what was signed for another chain
+        let other_chain = (
+            VOTE_SIGNING_CONTEXT,
+            sp_core::H256::repeat_byte(1),
+            &payload,
+        )
+            .encode();
+        assert_noop!(
+            QuadraticFunding::vote_signed(
+                Origin::signed(4),
+                payload.clone(),
+                TestSignature(3, other_chain),
+                3
+            ),
+            Error::<Runtime>::InvalidSignature
+        );
+        // the same payload can not be replayed
+        assert_noop!(
+            QuadraticFunding::vote_signed(
+                Origin::signed(4),
+                payload.clone(),
+                TestSignature(3, QuadraticFunding::vote_signing_payload(&payload)),
+                3
+            ),
+            Error::<Runtime>::InvalidNonce
+        );
+
+        let payload = VotePayload {
+            nonce: 1,
+            ..payload
+        };
+        System::set_block_number(11);
+        assert_noop!(
+            QuadraticFunding::vote_signed(
+                Origin::signed(4),
+                payload.clone(),
+                TestSignature(3, QuadraticFunding::vote_signing_payload(&payload)),
+                3
+            ),
+            Error::<Runtime>::VotePayloadExpired
+        );
+
+        // messages signed by wallets wrapping them in `<Bytes>` are accepted
+        System::set_block_number(10);
+        let wrapped = [
+            &b"<Bytes>"[..],
+            &QuadraticFunding::vote_signing_payload(&payload)[..],
+            &b"</Bytes>"[..],
+        ]
+        .concat();
+        assert_ok!(QuadraticFunding::vote_signed(
+            Origin::signed(4),
+            payload.clone(),
+            TestSignature(3, wrapped),
+            3
+        ));
+        assert_eq!(QuadraticFunding::vote_nonce(3), 2);
+    })
+}
+
+#[test]
+fn test_token_gated_round() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let project_hash = start_milestone_round(round_id);
+        let rule = EligibilityRule {
+            currency_id: CurrencyId::KSM,
+            min_balance: 1_000,
+            snapshot: None,
+        };
+        assert_noop!(
+            QuadraticFunding::set_eligibility_rule(Origin::signed(1), round_id, Some(rule.clone())),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(QuadraticFunding::set_eligibility_rule(
+            Origin::root(),
+            round_id,
+            Some(rule)
+        ));
+        assert_ok!(Currencies::deposit(CurrencyId::KSM, &1, 1_000));
+        assert_ok!(Currencies::deposit(CurrencyId::KSM, &3, 999));
+        assert_ok!(QuadraticFunding::vote(
+            Origin::signed(1),
+            CurrencyId::DORA,
+            round_id,
+            project_hash,
+            1
+        ));
+        assert_noop!(
+            QuadraticFunding::vote(
+                Origin::signed(3),
+                CurrencyId::DORA,
+                round_id,
+                project_hash,
+                1
+            ),
+            Error::<Runtime>::NotEligible
+        );
+        // the rule also applies to the votes cast on behalf of a voter
+        assert_ok!(QuadraticFunding::delegate_votes(
+            Origin::signed(3),
+            round_id,
+            1,
+            u128::MAX
+        ));
+        assert_noop!(
+            QuadraticFunding::vote_as_delegate(
+                Origin::signed(1),
+                CurrencyId::DORA,
+                round_id,
+                project_hash,
+                1,
+                3
+            ),
+            Error::<Runtime>::NotEligible
+        );
+        assert_ok!(QuadraticFunding::set_eligibility_rule(
+            Origin::root(),
+            round_id,
+            None
+        ));
+        assert_ok!(QuadraticFunding::vote(
+            Origin::signed(3),
+            CurrencyId::DORA,
+            round_id,
+            project_hash,
+            1
+        ));
+    })
+}
+
+#[test]
+fn test_token_gated_round_with_snapshot() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let project_hash = start_milestone_round(round_id);
+        assert_noop!(
+            QuadraticFunding::prove_eligibility(Origin::signed(1), round_id),
+            Error::<Runtime>::NoEligibilitySnapshot
+        );
+        assert_ok!(QuadraticFunding::set_eligibility_rule(
+            Origin::root(),
+            round_id,
+            Some(EligibilityRule {
+                currency_id: CurrencyId::KSM,
+                min_balance: 1_000,
+                snapshot: Some(10),
+            })
+        ));
+        assert_ok!(Currencies::deposit(CurrencyId::KSM, &1, 1_000));
+        assert_noop!(
+            QuadraticFunding::prove_eligibility(Origin::signed(3), round_id),
+            Error::<Runtime>::NotEligible
+        );
+        // holding at vote time is not enough, holdings must be proved before the snapshot
+        assert_noop!(
+            QuadraticFunding::vote(
+                Origin::signed(1),
+                CurrencyId::DORA,
+                round_id,
+                project_hash,
+                1
+            ),
+            Error::<Runtime>::NotEligible
+        );
+        assert_ok!(QuadraticFunding::prove_eligibility(
+            Origin::signed(1),
+            round_id
+        ));
+        assert_eq!(QuadraticFunding::eligible_voters(round_id, 1), Some(true));
+        assert_eq!(
+            QuadraticFunding::eligibility_locks(round_id, 1),
+            Some(CurrencyId::KSM)
+        );
+        // the proved holdings can not be passed to another account to prove it too
+        assert_noop!(
+            <Currencies as MultiCurrency<u64>>::transfer(CurrencyId::KSM, &1, &3, 1_000),
+            orml_tokens::Error::<Runtime>::LiquidityRestrictions
+        );
+        assert_noop!(
+            QuadraticFunding::prove_eligibility(Origin::signed(3), round_id),
+            Error::<Runtime>::NotEligible
+        );
+        assert_noop!(
+            QuadraticFunding::unlock_eligibility(Origin::signed(3), round_id, 1),
+            Error::<Runtime>::EligibilityLockNotExpired
+        );
+        System::set_block_number(11);
+        assert_noop!(
+            QuadraticFunding::unlock_eligibility(Origin::signed(3), round_id, 3),
+            Error::<Runtime>::EligibilityLockNotExist
+        );
+        assert_ok!(QuadraticFunding::unlock_eligibility(
+            Origin::signed(3),
+            round_id,
+            1
+        ));
+        assert_eq!(QuadraticFunding::eligibility_locks(round_id, 1), None);
+        assert_ok!(<Currencies as MultiCurrency<u64>>::transfer(
+            CurrencyId::KSM,
+            &1,
+            &3,
+            1_000
+        ));
+        assert_noop!(
+            QuadraticFunding::prove_eligibility(Origin::signed(3), round_id),
+            Error::<Runtime>::EligibilitySnapshotPassed
+        );
+        assert_ok!(QuadraticFunding::vote(
+            Origin::signed(1),
+            CurrencyId::DORA,
+            round_id,
+            project_hash,
+            1
+        ));
+        assert_noop!(
+            QuadraticFunding::vote(
+                Origin::signed(3),
+                CurrencyId::DORA,
+                round_id,
+                project_hash,
+                1
+            ),
+            Error::<Runtime>::NotEligible
+        );
+    })
+}
+
+#[test]
+fn test_start_round_from_template() {
+    new_test_ext().execute_with(|| {
+        let template = RoundTemplate {
+            name: b"season".to_vec().try_into().unwrap(),
+            currency_id: CurrencyId::DORA,
+            admin: 5,
+            round_reserve: 0,
+            matching: MatchingMode::Clr,
+            max_match_per_project: Perbill::from_percent(50),
+            eligibility: None,
+            limits: Default::default(),
+            cost_curve: Default::default(),
+            challenge_period: 10,
+            conviction_period: 0,
+        };
+        assert_noop!(
+            QuadraticFunding::create_round_template(Origin::signed(1), 1, template.clone()),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            QuadraticFunding::create_round_template(
+                Origin::root(),
+                1,
+                RoundTemplate {
+                    name: b"s".to_vec().try_into().unwrap(),
+                    ..template.clone()
+                }
+            ),
+            Error::<Runtime>::RoundNameTooShort
+        );
+        // the settings are checked like the setters of a round check them
+        for (invalid, error) in [
+            (
+                RoundTemplate {
+                    cost_curve: CostCurve::CappedQuadratic(0),
+                    ..template.clone()
+                },
+                Error::<Runtime>::InvalidCostCurve,
+            ),
+            (
+                RoundTemplate {
+                    matching: MatchingMode::PairwiseBounded(0),
+                    ..template.clone()
+                },
+                Error::<Runtime>::InvalidMatchingMode,
+            ),
+            (
+                RoundTemplate {
+                    matching: MatchingMode::SupportArea,
+                    cost_curve: CostCurve::Linear,
+                    ..template.clone()
+                },
+                Error::<Runtime>::InvalidCostCurve,
+            ),
+            (
+                RoundTemplate {
+                    challenge_period: ArchiveDelay::get() + 1,
+                    ..template.clone()
+                },
+                Error::<Runtime>::InvalidChallengePeriod,
+            ),
+        ] {
+            let error: DispatchError = error.into();
+            assert_noop!(
+                QuadraticFunding::create_round_template(Origin::root(), 1, invalid.clone()),
+                error
+            );
+            // nor can a round start from a template stored before the bounds changed
+            crate::RoundTemplates::<Runtime>::insert(2, invalid);
+            assert_noop!(
+                QuadraticFunding::start_round_from_template(Origin::root(), 1, 2),
+                error
+            );
+        }
+        crate::RoundTemplates::<Runtime>::remove(2);
+        assert_noop!(
+            QuadraticFunding::start_round_from_template(Origin::root(), 1, 1),
+            Error::<Runtime>::TemplateNotExist
+        );
+        assert_ok!(QuadraticFunding::create_round_template(
+            Origin::root(),
+            1,
+            template.clone()
+        ));
+        assert_noop!(
+            QuadraticFunding::create_round_template(Origin::root(), 1, template.clone()),
+            Error::<Runtime>::TemplateExisted
+        );
+
+        for round_id in [1, 2] {
+            assert_ok!(QuadraticFunding::start_round_from_template(
+                Origin::root(),
+                round_id,
+                1
+            ));
+            let round = QuadraticFunding::rounds(round_id).unwrap();
+            assert!(round.ongoing);
+            assert_eq!(round.admin, 5);
+            assert_eq!(round.matching, MatchingMode::Clr);
+            assert_eq!(round.max_match_per_project, Perbill::from_percent(50));
+            assert_eq!(QuadraticFunding::challenge_period(round_id), 10);
+            assert_eq!(QuadraticFunding::conviction_period(round_id), 0);
+        }
+        assert_noop!(
+            QuadraticFunding::start_round_from_template(Origin::root(), 1, 1),
+            Error::<Runtime>::RoundExisted
+        );
+
+        assert_ok!(QuadraticFunding::remove_round_template(Origin::root(), 1));
+        assert_noop!(
+            QuadraticFunding::remove_round_template(Origin::root(), 1),
+            Error::<Runtime>::TemplateNotExist
+        );
+    })
+}
+
+#[test]
+fn test_clone_round() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            QuadraticFunding::clone_round(Origin::root(), 2, 1),
+            Error::<Runtime>::RoundNotExist
+        );
+        start_sponsored_round(1);
+        assert_ok!(QuadraticFunding::set_matching_mode(
+            Origin::root(),
+            1,
+            MatchingMode::Clr
+        ));
+        assert_ok!(QuadraticFunding::set_conviction_period(
+            Origin::root(),
+            1,
+            20
+        ));
+        assert_ok!(QuadraticFunding::donate(
+            Origin::signed(1),
+            1,
+            1_000_000_000_000_000,
+            CurrencyId::DORA,
+        ));
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            1,
+            QuadraticFunding::round_witness(1)
+        ));
+
+        assert_noop!(
+            QuadraticFunding::clone_round(Origin::signed(1), 2, 1),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(QuadraticFunding::clone_round(Origin::root(), 2, 1));
+        let prev_round = QuadraticFunding::rounds(1).unwrap();
+        let round = QuadraticFunding::rounds(2).unwrap();
+        // only the settings are cloned, the new round starts empty
+        assert!(round.ongoing);
+        assert_eq!(round.name, prev_round.name);
+        assert_eq!(round.admin, prev_round.admin);
+        assert_eq!(round.matching, MatchingMode::Clr);
+        assert_eq!(round.support_pool, 0);
+        assert_eq!(round.sponsors, 0);
+        assert_eq!(QuadraticFunding::conviction_period(2), 20);
+        assert_noop!(
+            QuadraticFunding::clone_round(Origin::root(), 2, 1),
+            Error::<Runtime>::RoundExisted
+        );
+    })
+}
+
+#[test]
+fn test_archive_and_prune_round() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let project_hash = start_milestone_round(round_id);
+        assert_ok!(QuadraticFunding::set_conviction_period(
+            Origin::root(),
+            round_id,
+            10
+        ));
+        assert_ok!(QuadraticFunding::vote(
+            Origin::signed(1),
+            CurrencyId::DORA,
+            round_id,
+            project_hash,
+            1
+        ));
+        assert_ok!(QuadraticFunding::vote_with_conviction(
+            Origin::signed(3),
+            CurrencyId::DORA,
+            round_id,
+            project_hash,
+            1,
+            Conviction::Locked2x
+        ));
+        assert_noop!(
+            QuadraticFunding::archive_round(Origin::signed(4), round_id),
+            Error::<Runtime>::RoundIsOngoing
+        );
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            round_id,
+            QuadraticFunding::round_witness(round_id)
+        ));
+        assert_noop!(
+            QuadraticFunding::archive_round(Origin::signed(4), round_id),
+            Error::<Runtime>::ArchiveDelayNotOver
+        );
+        System::set_block_number(1 + ArchiveDelay::get());
+        // the conviction voter did not get their funds back yet
+        assert_noop!(
+            QuadraticFunding::archive_round(Origin::signed(4), round_id),
+            Error::<Runtime>::PayoutsPending
+        );
+        assert_ok!(QuadraticFunding::unlock_conviction(
+            Origin::signed(4),
+            round_id,
+            3
+        ));
+        assert_ok!(QuadraticFunding::archive_round(Origin::signed(4), round_id));
+        assert!(QuadraticFunding::rounds(round_id).is_none());
+        let summary = QuadraticFunding::archived_rounds(round_id).unwrap();
+        assert_eq!(summary.admin, 5);
+        assert_eq!(summary.ended_at, 1);
+        // an archived round can not be started again
+        assert_noop!(
+            QuadraticFunding::start_round(
+                Origin::root(),
+                round_id,
+                CurrencyId::DORA,
+                "doraRound".to_string().into(),
+                5,
+                0
+            ),
+            Error::<Runtime>::RoundExisted
+        );
+
+        // only the project fits in the weight of this block
+        let project = QuadraticFunding::projects(round_id, project_hash).unwrap();
+        QuadraticFunding::on_idle(System::block_number(), <() as WeightInfo>::prune_round(1));
+        assert!(QuadraticFunding::projects(round_id, project_hash).is_none());
+        let project_summary = QuadraticFunding::archived_projects(round_id, project_hash).unwrap();
+        assert_eq!(project_summary.owner, 2);
+        assert_eq!(project_summary.total_votes, 2);
+        assert_eq!(project_summary.support_fund, project.support_fund);
+        assert_eq!(
+            crate::ProjectVotes::<Runtime>::iter_prefix((round_id,)).count(),
+            2
+        );
+
+        QuadraticFunding::on_idle(System::block_number(), u64::MAX);
+        assert_eq!(
+            crate::ProjectVotes::<Runtime>::iter_prefix((round_id,)).count(),
+            0
+        );
+        assert_eq!(
+            crate::BoostedVotes::<Runtime>::iter_prefix((round_id,)).count(),
+            0
+        );
+        assert_eq!(QuadraticFunding::sponsorships(round_id, 1), None);
+        assert_eq!(QuadraticFunding::conviction_period(round_id), 0);
+        assert_eq!(crate::RoundsToPrune::<Runtime>::iter().count(), 0);
+        assert!(QuadraticFunding::archived_rounds(round_id).is_some());
+        System::assert_last_event(Event::QuadraticFunding(
+            crate::Event::<Runtime>::RoundPruned(round_id),
+        ));
+    })
+}
+
+#[test]
+fn test_voter_participation() {
+    new_test_ext().execute_with(|| {
+        let projects: Vec<_> = (0..3u128)
+            .map(|i| BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), i)))
+            .collect();
+        for round_id in [1, 2] {
+            start_sponsored_round(round_id);
+            for project_hash in &projects {
+                assert_ok!(QuadraticFunding::register_project(
+                    Origin::signed(2),
+                    round_id,
+                    *project_hash,
+                    "project".to_string().into()
+                ));
+            }
+        }
+        for (round_id, project_hash, ballot) in [
+            (1, projects[0], 1),
+            (1, projects[1], 1),
+            (1, projects[0], 2),
+            (2, projects[2], 1),
+        ] {
+            assert_ok!(QuadraticFunding::vote(
+                Origin::signed(1),
+                CurrencyId::DORA,
+                round_id,
+                project_hash,
+                ballot
+            ));
+        }
+        // a voter can only vote for `MaxVotedProjects` projects per round
+        assert_noop!(
+            QuadraticFunding::vote(Origin::signed(1), CurrencyId::DORA, 1, projects[2], 1),
+            Error::<Runtime>::TooManyVotedProjects
+        );
+
+        let participation = QuadraticFunding::voter_participation(1, 1).unwrap();
+        assert_eq!(
+            participation.projects.into_inner(),
+            vec![projects[0], projects[1]]
+        );
+        // 1 + 1 + (2 + 3) units of vote
+        assert_eq!(participation.spent, 7_000_000_000_000);
+        assert_eq!(QuadraticFunding::participation_in_round(1, 3), None);
+        assert_eq!(
+            QuadraticFunding::voter_history(1, 0, 10),
+            vec![
+                (
+                    1,
+                    vec![(projects[0], 3), (projects[1], 1)],
+                    7_000_000_000_000
+                ),
+                (2, vec![(projects[2], 1)], 1_000_000_000_000),
+            ]
+        );
+        assert!(QuadraticFunding::voter_history(3, 0, 10).is_empty());
+        // only the rounds of the range are looked up
+        assert_eq!(
+            QuadraticFunding::voter_history(1, 2, 10),
+            vec![(2, vec![(projects[2], 1)], 1_000_000_000_000)]
+        );
+        assert_eq!(QuadraticFunding::voter_history(1, 1, 1).len(), 1);
+        assert!(QuadraticFunding::voter_history(1, 3, u32::MAX).is_empty());
+    })
+}
+
+#[test]
+fn test_max_voters_per_project() {
+    new_test_ext().execute_with(|| {
+        MaxVotersPerProject::set(2);
+        let round_id = 1;
+        let project_hash = start_milestone_round(round_id);
+        assert_ok!(QuadraticFunding::set_matching_mode(
+            Origin::root(),
+            round_id,
+            MatchingMode::PairwiseBounded(DOLLARS)
+        ));
+        for who in [1, 2] {
+            assert_ok!(QuadraticFunding::vote(
+                Origin::signed(who),
+                CurrencyId::DORA,
+                round_id,
+                project_hash,
+                1
+            ));
+        }
+        // the voters of the project can still add ballots
+        assert_ok!(QuadraticFunding::vote(
+            Origin::signed(1),
+            CurrencyId::DORA,
+            round_id,
+            project_hash,
+            1
+        ));
+        assert_noop!(
+            QuadraticFunding::vote(
+                Origin::signed(3),
+                CurrencyId::DORA,
+                round_id,
+                project_hash,
+                1
+            ),
+            Error::<Runtime>::TooManyVoters
+        );
+        assert_eq!(QuadraticFunding::project_voters(round_id, project_hash), 2);
+        assert_eq!(QuadraticFunding::round_size(round_id).voters_per_project, 2);
+
+        // the other matching modes do not go over the pairs, their projects are not bounded
+        let round_id = 2;
+        let project_hash = start_milestone_round(round_id);
+        for who in [1, 2, 3] {
+            assert_ok!(QuadraticFunding::vote(
+                Origin::signed(who),
+                CurrencyId::DORA,
+                round_id,
+                project_hash,
+                1
+            ));
+        }
+        assert_eq!(QuadraticFunding::project_voters(round_id, project_hash), 3);
+        // and can not switch to the pairwise matching once they have too many voters
+        assert_noop!(
+            QuadraticFunding::set_matching_mode(
+                Origin::root(),
+                round_id,
+                MatchingMode::PairwiseBounded(DOLLARS)
+            ),
+            Error::<Runtime>::TooManyVoters
+        );
+    })
+}
+
+#[test]
+fn test_voter_limits() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let project_hash = start_milestone_round(round_id);
+        let project_b = BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 1u128));
+        assert_ok!(QuadraticFunding::register_project(
+            Origin::signed(2),
+            round_id,
+            project_b,
+            "project".to_string().into()
+        ));
+        let limits = VoterLimits {
+            max_spend: Some(5_000_000_000_000),
+            max_ballots_per_project: Some(2),
+        };
+        assert_noop!(
+            QuadraticFunding::set_voter_limits(Origin::signed(1), round_id, limits.clone()),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(QuadraticFunding::set_voter_limits(
+            Origin::root(),
+            round_id,
+            limits
+        ));
+        assert_noop!(
+            QuadraticFunding::vote(
+                Origin::signed(1),
+                CurrencyId::DORA,
+                round_id,
+                project_hash,
+                3
+            ),
+            Error::<Runtime>::TooManyBallots
+        );
+        assert_ok!(QuadraticFunding::vote(
+            Origin::signed(1),
+            CurrencyId::DORA,
+            round_id,
+            project_hash,
+            1
+        ));
+        assert_ok!(QuadraticFunding::vote(
+            Origin::signed(1),
+            CurrencyId::DORA,
+            round_id,
+            project_hash,
+            1
+        ));
+        assert_noop!(
+            QuadraticFunding::vote(
+                Origin::signed(1),
+                CurrencyId::DORA,
+                round_id,
+                project_hash,
+                1
+            ),
+            Error::<Runtime>::TooManyBallots
+        );
+        // 3 units of vote are spent, 2 more ballots would cost 3 more
+        assert_noop!(
+            QuadraticFunding::vote(Origin::signed(1), CurrencyId::DORA, round_id, project_b, 2),
+            Error::<Runtime>::SpendingCapExceeded
+        );
+        assert_ok!(QuadraticFunding::vote(
+            Origin::signed(1),
+            CurrencyId::DORA,
+            round_id,
+            project_b,
+            1
+        ));
+        // the limits apply to each voter separately
+        assert_ok!(QuadraticFunding::vote(
+            Origin::signed(3),
+            CurrencyId::DORA,
+            round_id,
+            project_hash,
+            2
+        ));
+
+        // cloned rounds keep the limits
+        assert_ok!(QuadraticFunding::clone_round(Origin::root(), 2, round_id));
+        assert_eq!(
+            QuadraticFunding::rounds(2)
+                .unwrap()
+                .limits
+                .max_ballots_per_project,
+            Some(2)
+        );
+    })
+}
+
+#[test]
+fn test_cost_curves() {
+    let curves: Vec<Box<dyn VoteCostCurve>> = vec![
+        Box::new(Quadratic),
+        Box::new(Linear),
+        Box::new(CappedQuadratic { cap: 5 }),
+        Box::new(Exponential),
+    ];
+    for curve in curves.iter() {
+        let mut last_marginal = 0;
+        for voted in 0..40u128 {
+            // marginal costs never decrease
+            let marginal = curve.cost(voted, 1);
+            assert!(marginal >= last_marginal);
+            assert!(marginal > 0);
+            last_marginal = marginal;
+            // splitting ballots in several votes costs the same
+            for a in 0..10u128 {
+                for b in 0..10u128 {
+                    assert_eq!(
+                        curve.cost(voted, a + b),
+                        curve.cost(voted, a) + curve.cost(voted + a, b)
+                    );
+                }
+            }
+        }
+    }
+    for voted in 0..40u128 {
+        for ballot in 0..40u128 {
+            assert_eq!(
+                Quadratic.cost(voted, ballot),
+                QuadraticFunding::cal_cost(voted, ballot)
+            );
+            assert_eq!(
+                CappedQuadratic { cap: u128::MAX }.cost(voted, ballot),
+                Quadratic.cost(voted, ballot)
+            );
+        }
+    }
+    assert_eq!(CappedQuadratic { cap: 3 }.cost(0, 5), 1 + 2 + 3 + 3 + 3);
+    assert_eq!(Exponential.cost(2, 3), 4 + 8 + 16);
+    assert_eq!(Exponential.cost(0, 200), u128::MAX);
+    assert_eq!(CostCurve::default(), CostCurve::Quadratic);
+}
+
+#[test]
+fn test_set_cost_curve() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let project_hash = start_milestone_round(round_id);
+        assert_noop!(
+            QuadraticFunding::set_cost_curve(Origin::signed(1), round_id, CostCurve::Linear),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            QuadraticFunding::set_cost_curve(Origin::root(), 2, CostCurve::Linear),
+            Error::<Runtime>::RoundNotExist
+        );
+        assert_noop!(
+            QuadraticFunding::set_cost_curve(
+                Origin::root(),
+                round_id,
+                CostCurve::CappedQuadratic(0)
+            ),
+            Error::<Runtime>::InvalidCostCurve
+        );
+        // the support area only follows what the voters paid under the quadratic curve
+        for curve in [
+            CostCurve::Linear,
+            CostCurve::CappedQuadratic(3),
+            CostCurve::Exponential,
+        ] {
+            assert_noop!(
+                QuadraticFunding::set_cost_curve(Origin::root(), round_id, curve),
+                Error::<Runtime>::InvalidCostCurve
+            );
+        }
+        assert_ok!(QuadraticFunding::set_matching_mode(
+            Origin::root(),
+            round_id,
+            MatchingMode::Clr
+        ));
+        assert_ok!(QuadraticFunding::set_cost_curve(
+            Origin::root(),
+            round_id,
+            CostCurve::Linear
+        ));
+        assert_noop!(
+            QuadraticFunding::set_matching_mode(
+                Origin::root(),
+                round_id,
+                MatchingMode::SupportArea
+            ),
+            Error::<Runtime>::InvalidMatchingMode
+        );
+        assert_eq!(
+            QuadraticFunding::rounds(round_id).unwrap().cost_curve,
+            CostCurve::Linear
+        );
+
+        // every ballot costs one unit of vote
+        let before = <Currencies as MultiCurrency<u64>>::free_balance(CurrencyId::DORA, &1);
+        assert_ok!(QuadraticFunding::vote(
+            Origin::signed(1),
+            CurrencyId::DORA,
+            round_id,
+            project_hash,
+            3
+        ));
+        assert_ok!(QuadraticFunding::vote(
+            Origin::signed(1),
+            CurrencyId::DORA,
+            round_id,
+            project_hash,
+            2
+        ));
+        let after = <Currencies as MultiCurrency<u64>>::free_balance(CurrencyId::DORA, &1);
+        assert_eq!(
+            before - after,
+            QuadraticFunding::cal_amount(5, false).unwrap()
+        );
+        // the matching formulas see what the voter paid under the round's curve
+        let contributions = QuadraticFunding::project_contributions(round_id, project_hash);
+        assert_eq!(contributions.len(), 1);
+        assert_eq!(contributions[0].ballots, 5);
+        assert_eq!(
+            contributions[0].amount,
+            QuadraticFunding::cal_amount(5, false).unwrap()
+        );
+
+        // the pricing can not change once the round has votes
+        assert_noop!(
+            QuadraticFunding::set_cost_curve(Origin::root(), round_id, CostCurve::Quadratic),
+            Error::<Runtime>::RoundAlreadyVoted
+        );
+
+        // cloned rounds keep the curve
+        assert_ok!(QuadraticFunding::clone_round(Origin::root(), 2, round_id));
+        assert_eq!(
+            QuadraticFunding::rounds(2).unwrap().cost_curve,
+            CostCurve::Linear
+        );
+
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            round_id,
+            QuadraticFunding::round_witness(round_id)
+        ));
+        assert_noop!(
+            QuadraticFunding::set_cost_curve(Origin::root(), round_id, CostCurve::Exponential),
+            Error::<Runtime>::RoundHasEnded
+        );
+    })
+}
+
+#[test]
+fn test_vote_cost_overflow() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let project_hash = start_milestone_round(round_id);
+        assert_ok!(QuadraticFunding::set_matching_mode(
+            Origin::root(),
+            round_id,
+            MatchingMode::Clr
+        ));
+        assert_ok!(QuadraticFunding::set_cost_curve(
+            Origin::root(),
+            round_id,
+            CostCurve::Exponential
+        ));
+        // the cost of 200 ballots saturates, it can not be paid
+        assert_noop!(
+            QuadraticFunding::vote(
+                Origin::signed(1),
+                CurrencyId::DORA,
+                round_id,
+                project_hash,
+                200
+            ),
+            Error::<Runtime>::InvalidBallot
+        );
+        assert_ok!(QuadraticFunding::delegate_votes(
+            Origin::signed(3),
+            round_id,
+            4,
+            6_000_000_000_000
+        ));
+        assert_noop!(
+            QuadraticFunding::vote_as_delegate(
+                Origin::signed(4),
+                CurrencyId::DORA,
+                round_id,
+                project_hash,
+                200,
+                3
+            ),
+            Error::<Runtime>::InvalidBallot
+        );
+    })
+}
+
+#[test]
+fn test_round_currencies() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let project_hash = start_milestone_round(round_id);
+        let rate = CurrencyRate::Fixed(FixedU128::saturating_from_integer(2u128));
+        assert_noop!(
+            QuadraticFunding::set_round_currency(
+                Origin::signed(1),
+                round_id,
+                CurrencyId::KSM,
+                Some(rate)
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            QuadraticFunding::set_round_currency(Origin::root(), 2, CurrencyId::KSM, Some(rate)),
+            Error::<Runtime>::RoundNotExist
+        );
+        assert_noop!(
+            QuadraticFunding::set_round_currency(
+                Origin::root(),
+                round_id,
+                CurrencyId::DORA,
+                Some(rate)
+            ),
+            Error::<Runtime>::InvalidRoundCurrency
+        );
+        assert_noop!(
+            QuadraticFunding::set_round_currency(
+                Origin::root(),
+                round_id,
+                CurrencyId::KSM,
+                Some(CurrencyRate::Fixed(FixedU128::zero()))
+            ),
+            Error::<Runtime>::InvalidRoundCurrency
+        );
+        assert_ok!(<Currencies as MultiCurrency<u64>>::deposit(
+            CurrencyId::KSM,
+            &3,
+            100 * DOLLARS
+        ));
+        assert_noop!(
+            QuadraticFunding::vote(
+                Origin::signed(3),
+                CurrencyId::KSM,
+                round_id,
+                project_hash,
+                2
+            ),
+            Error::<Runtime>::MismatchingCurencyId
+        );
+        assert_ok!(QuadraticFunding::set_round_currency(
+            Origin::root(),
+            round_id,
+            CurrencyId::KSM,
+            Some(rate)
+        ));
+        assert_eq!(
+            QuadraticFunding::round_currency(round_id, CurrencyId::KSM),
+            Some(rate)
+        );
+
+        // the ballots are priced in DORA, one KSM is worth 2 DORA
+        assert_ok!(QuadraticFunding::vote(
+            Origin::signed(3),
+            CurrencyId::KSM,
+            round_id,
+            project_hash,
+            2
+        ));
+        let paid =
+            100 * DOLLARS - <Currencies as MultiCurrency<u64>>::free_balance(CurrencyId::KSM, &3);
+        assert_eq!(paid, QuadraticFunding::cal_amount(3, false).unwrap() / 2);
+        assert_eq!(
+            QuadraticFunding::projects(round_id, project_hash)
+                .unwrap()
+                .grants,
+            QuadraticFunding::cal_amount(3, false).unwrap()
+                - QuadraticFunding::cal_amount(3, true).unwrap()
+        );
+        assert_eq!(
+            QuadraticFunding::voter_participation(round_id, 3)
+                .unwrap()
+                .spent,
+            QuadraticFunding::cal_amount(3, false).unwrap()
+        );
+
+        // conviction locks are kept in DORA
+        assert_ok!(QuadraticFunding::set_conviction_period(
+            Origin::root(),
+            round_id,
+            10
+        ));
+        assert_noop!(
+            QuadraticFunding::vote_with_conviction(
+                Origin::signed(3),
+                CurrencyId::KSM,
+                round_id,
+                project_hash,
+                1,
+                Conviction::Locked2x
+            ),
+            Error::<Runtime>::MismatchingCurencyId
+        );
+
+        // at the oracle rate, one KSM is worth 20 DORA
+        assert_ok!(QuadraticFunding::set_round_currency(
+            Origin::root(),
+            round_id,
+            CurrencyId::KSM,
+            Some(CurrencyRate::Oracle)
+        ));
+        let before = <Currencies as MultiCurrency<u64>>::free_balance(CurrencyId::KSM, &3);
+        assert_ok!(QuadraticFunding::vote(
+            Origin::signed(3),
+            CurrencyId::KSM,
+            round_id,
+            project_hash,
+            1
+        ));
+        let after = <Currencies as MultiCurrency<u64>>::free_balance(CurrencyId::KSM, &3);
+        assert_eq!(
+            before - after,
+            QuadraticFunding::cal_amount(3, false).unwrap() / 20
+        );
+        KsmPrice::set(None);
+        assert_noop!(
+            QuadraticFunding::vote(
+                Origin::signed(3),
+                CurrencyId::KSM,
+                round_id,
+                project_hash,
+                1
+            ),
+            Error::<Runtime>::PriceNotAvailable
+        );
+        KsmPrice::set(Some(FixedU128::saturating_from_integer(20u128)));
+
+        assert_ok!(QuadraticFunding::set_round_currency(
+            Origin::root(),
+            round_id,
+            CurrencyId::KSM,
+            None
+        ));
+        assert_noop!(
+            QuadraticFunding::vote(
+                Origin::signed(3),
+                CurrencyId::KSM,
+                round_id,
+                project_hash,
+                1
+            ),
+            Error::<Runtime>::MismatchingCurencyId
+        );
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            round_id,
+            QuadraticFunding::round_witness(round_id)
+        ));
+        assert_noop!(
+            QuadraticFunding::set_round_currency(
+                Origin::root(),
+                round_id,
+                CurrencyId::KSM,
+                Some(rate)
+            ),
+            Error::<Runtime>::RoundHasEnded
+        );
+    })
+}
+
+#[test]
+fn test_multi_currency_payouts() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let project_hash = start_milestone_round(round_id);
+        assert_ok!(QuadraticFunding::set_round_currency(
+            Origin::root(),
+            round_id,
+            CurrencyId::KSM,
+            Some(CurrencyRate::Fixed(FixedU128::saturating_from_integer(
+                2u128
+            )))
+        ));
+        assert_ok!(<Currencies as MultiCurrency<u64>>::deposit(
+            CurrencyId::KSM,
+            &3,
+            10 * DOLLARS
+        ));
+        // 994 DORA and 497 KSM are left in the pool after the fees
+        assert_ok!(QuadraticFunding::donate(
+            Origin::signed(3),
+            round_id,
+            5 * DOLLARS,
+            CurrencyId::KSM
+        ));
+        let round = QuadraticFunding::rounds(round_id).unwrap();
+        assert_eq!(round.support_pool, 2 * 994_000_000_000_000);
+        assert_eq!(round.total_tax, 2 * 6_000_000_000_000);
+        assert_eq!(
+            QuadraticFunding::round_holding(round_id, CurrencyId::KSM),
+            497_000_000_000_000
+        );
+        assert_eq!(
+            QuadraticFunding::round_held_value(round_id),
+            round.support_pool
+        );
+
+        assert_ok!(QuadraticFunding::vote(
+            Origin::signed(1),
+            CurrencyId::DORA,
+            round_id,
+            project_hash,
+            1
+        ));
+        assert_ok!(QuadraticFunding::vote(
+            Origin::signed(3),
+            CurrencyId::DORA,
+            round_id,
+            project_hash,
+            1
+        ));
+        // the only project gets the whole pool, paid in both currencies
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            round_id,
+            QuadraticFunding::round_witness(round_id)
+        ));
+        let round_account = QuadraticFunding::round_account(round_id);
+        assert_eq!(
+            <Currencies as MultiCurrency<u64>>::free_balance(CurrencyId::KSM, &5),
+            497_000_000_000_000
+        );
+        assert_eq!(
+            <Currencies as MultiCurrency<u64>>::free_balance(CurrencyId::KSM, &round_account),
+            0
+        );
+        assert_eq!(
+            <Currencies as MultiCurrency<u64>>::free_balance(CurrencyId::DORA, &round_account),
+            0
+        );
+        assert_eq!(QuadraticFunding::round_held_value(round_id), 0);
+    })
+}
+
+#[test]
+fn test_grants_in_other_currency_are_held_until_settled() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        start_sponsored_round(round_id);
+        let project_hash = BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 0u128));
+        assert_ok!(QuadraticFunding::register_project(
+            Origin::signed(2),
+            round_id,
+            project_hash,
+            "project".to_string().into()
+        ));
+        assert_ok!(QuadraticFunding::set_round_currency(
+            Origin::root(),
+            round_id,
+            CurrencyId::KSM,
+            Some(CurrencyRate::Fixed(FixedU128::saturating_from_integer(
+                2u128
+            )))
+        ));
+        assert_ok!(<Currencies as MultiCurrency<u64>>::deposit(
+            CurrencyId::KSM,
+            &3,
+            10 * DOLLARS
+        ));
+        assert_ok!(QuadraticFunding::vote(
+            Origin::signed(3),
+            CurrencyId::KSM,
+            round_id,
+            project_hash,
+            1
+        ));
+        // the admin accounts in DORA, the grants paid in KSM wait in the round account
+        let grants = QuadraticFunding::cal_amount(1, false).unwrap()
+            - QuadraticFunding::cal_amount(1, true).unwrap();
+        let round_account = QuadraticFunding::round_account(round_id);
+        assert_eq!(
+            <Currencies as MultiCurrency<u64>>::free_balance(CurrencyId::KSM, &5),
+            0
+        );
+        assert_eq!(
+            <Currencies as MultiCurrency<u64>>::free_balance(CurrencyId::KSM, &round_account),
+            grants / 2
+        );
+        assert_eq!(
+            QuadraticFunding::held_grants(round_id, project_hash),
+            grants
+        );
+        assert_eq!(QuadraticFunding::round_held_value(round_id), grants);
+
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            round_id,
+            QuadraticFunding::round_witness(round_id)
+        ));
+        assert_eq!(
+            <Currencies as MultiCurrency<u64>>::free_balance(CurrencyId::KSM, &5),
+            grants / 2
+        );
+        assert_eq!(
+            <Currencies as MultiCurrency<u64>>::free_balance(CurrencyId::KSM, &round_account),
+            0
+        );
+        assert_eq!(QuadraticFunding::round_held_value(round_id), 0);
+    })
+}
+
+#[test]
+fn test_round_hooks() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        assert_ok!(QuadraticFunding::start_round(
+            Origin::root(),
+            round_id,
+            CurrencyId::DORA,
+            "doraRound".to_string().into(),
+            5,
+            0
+        ));
+        assert_eq!(StartedRounds::get(), vec![(round_id, 5)]);
+        let first = BlakeTwo256::hash_of(&1);
+        let second = BlakeTwo256::hash_of(&2);
+        for hash in [first, second] {
+            assert_ok!(QuadraticFunding::register_project(
+                Origin::signed(1),
+                round_id,
+                hash,
+                "project".to_string().into()
+            ));
+        }
+        assert_ok!(QuadraticFunding::vote(
+            Origin::signed(2),
+            CurrencyId::DORA,
+            round_id,
+            first,
+            2
+        ));
+        assert_ok!(QuadraticFunding::vote(
+            Origin::signed(3),
+            CurrencyId::DORA,
+            round_id,
+            first,
+            1
+        ));
+        // failed votes are not counted
+        assert!(QuadraticFunding::vote(
+            Origin::signed(3),
+            CurrencyId::DORA,
+            round_id,
+            BlakeTwo256::hash_of(&3),
+            1
+        )
+        .is_err());
+        assert_eq!(
+            CountedVotes::get(),
+            vec![
+                (
+                    round_id,
+                    first,
+                    2,
+                    2,
+                    QuadraticFunding::cal_amount(3, false).unwrap()
+                ),
+                (
+                    round_id,
+                    first,
+                    3,
+                    1,
+                    QuadraticFunding::cal_amount(1, false).unwrap()
+                ),
+            ]
+        );
+        assert!(EndedRounds::get().is_empty());
+
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            round_id,
+            QuadraticFunding::round_witness(round_id)
+        ));
+        let ended = EndedRounds::get();
+        assert_eq!(ended.len(), 1);
+        assert_eq!(ended[0].0, round_id);
+        let mut results = ended[0].1.clone();
+        results.sort_by_key(|result| result.total_votes);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].hash, second);
+        assert_eq!(results[0].total_votes, 0);
+        assert_eq!(results[0].grants, 0);
+        assert_eq!(results[1].hash, first);
+        assert_eq!(results[1].owner, 1);
+        assert_eq!(results[1].total_votes, 3);
+        assert_eq!(
+            results[1].grants,
+            QuadraticFunding::cal_amount(3, false).unwrap()
+                - QuadraticFunding::cal_amount(3, true).unwrap()
+                + QuadraticFunding::cal_amount(1, false).unwrap()
+                - QuadraticFunding::cal_amount(1, true).unwrap()
+        );
+        assert_eq!(
+            results[1].support_fund,
+            QuadraticFunding::projects(round_id, first)
+                .unwrap()
+                .support_fund
+        );
+    })
+}
+
+#[test]
+fn test_quadratic_funding_interface() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let name: Vec<u8> = "doraRound".to_string().into();
+        assert_noop!(
+            <QuadraticFunding as QuadraticFundingInterface<_, _, _>>::create_round(
+                Origin::signed(1),
+                round_id,
+                CurrencyId::DORA,
+                name.clone(),
+                1,
+                0
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(QuadraticFunding::create_round(
+            Origin::root(),
+            round_id,
+            CurrencyId::DORA,
+            name.clone(),
+            1,
+            0
+        ));
+        assert!(QuadraticFunding::rounds(round_id).unwrap().ongoing);
+
+        // projects are registered by their owner or by the admin origin
+        let first = BlakeTwo256::hash_of(&1);
+        let second = BlakeTwo256::hash_of(&2);
+        assert_noop!(
+            QuadraticFunding::register_project_for(
+                Origin::signed(2),
+                1,
+                round_id,
+                first,
+                name.clone()
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(QuadraticFunding::register_project_for(
+            Origin::signed(1),
+            1,
+            round_id,
+            first,
+            name.clone()
+        ));
+        assert_ok!(QuadraticFunding::register_project_for(
+            Origin::root(),
+            2,
+            round_id,
+            second,
+            name.clone()
+        ));
+        assert_eq!(
+            QuadraticFunding::projects(round_id, second).unwrap().owner,
+            2
+        );
+
+        // votes are cast by the voter or by its delegate
+        assert_ok!(QuadraticFunding::vote_for(
+            Origin::signed(2),
+            2,
+            CurrencyId::DORA,
+            round_id,
+            first,
+            2
+        ));
+        assert_noop!(
+            QuadraticFunding::vote_for(Origin::signed(3), 2, CurrencyId::DORA, round_id, second, 1),
+            Error::<Runtime>::DelegationNotExist
+        );
+        assert_ok!(QuadraticFunding::delegate_votes(
+            Origin::signed(2),
+            round_id,
+            3,
+            10 * DOLLARS
+        ));
+        assert_ok!(QuadraticFunding::vote_for(
+            Origin::signed(3),
+            2,
+            CurrencyId::DORA,
+            round_id,
+            second,
+            1
+        ));
+        assert_eq!(
+            QuadraticFunding::voter_participation(round_id, 2)
+                .unwrap()
+                .projects
+                .len(),
+            2
+        );
+
+        // results are only known once the round is ended
+        assert_eq!(QuadraticFunding::round_results(round_id), None);
+        assert_eq!(QuadraticFunding::round_results(2), None);
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            round_id,
+            QuadraticFunding::round_witness(round_id)
+        ));
+        let mut results = QuadraticFunding::round_results(round_id).unwrap();
+        results.sort_by_key(|result| result.total_votes);
+        assert_eq!(results.len(), 2);
+        assert_eq!((results[0].hash, results[0].total_votes), (second, 1));
+        assert_eq!((results[1].hash, results[1].total_votes), (first, 2));
+
+        // and are still available once the round is archived
+        System::set_block_number(System::block_number() + ArchiveDelay::get());
+        assert_ok!(QuadraticFunding::archive_round(Origin::signed(4), round_id));
+        for _ in 0..2 {
+            let mut archived = QuadraticFunding::round_results(round_id).unwrap();
+            archived.sort_by_key(|result| result.total_votes);
+            assert_eq!(archived, results);
+            QuadraticFunding::on_idle(System::block_number(), u64::MAX);
+        }
+    })
+}
+
+#[test]
+fn test_participation_badges() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        assert_ok!(QuadraticFunding::start_round(
+            Origin::root(),
+            round_id,
+            CurrencyId::DORA,
+            "doraRound".to_string().into(),
+            5,
+            0
+        ));
+        let first = BlakeTwo256::hash_of(&1);
+        let second = BlakeTwo256::hash_of(&2);
+        assert_ok!(QuadraticFunding::register_project(
+            Origin::signed(1),
+            round_id,
+            first,
+            "project".to_string().into()
+        ));
+        assert_ok!(QuadraticFunding::register_project(
+            Origin::signed(2),
+            round_id,
+            second,
+            "project".to_string().into()
+        ));
+        for voter in [2, 3] {
+            assert_ok!(QuadraticFunding::vote(
+                Origin::signed(voter),
+                CurrencyId::DORA,
+                round_id,
+                first,
+                2
+            ));
+        }
+        assert_noop!(
+            QuadraticFunding::claim_voter_badge(Origin::signed(2), round_id),
+            Error::<Runtime>::RoundIsOngoing
+        );
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            round_id,
+            QuadraticFunding::round_witness(round_id)
+        ));
+        // the voters are kept once the round is ended
+        assert_eq!(
+            QuadraticFunding::round_participants(round_id, 2),
+            Some(false)
+        );
+        assert_eq!(
+            QuadraticFunding::round_participants(round_id, 3),
+            Some(false)
+        );
+
+        // voters claim their badge once
+        assert_ok!(QuadraticFunding::claim_voter_badge(
+            Origin::signed(2),
+            round_id
+        ));
+        let collection = BadgeCollection::get();
+        assert_eq!(<Uniques as Inspect<u64>>::owner(&collection, &0), Some(2));
+        assert_eq!(
+            QuadraticFunding::badge(0),
+            Some(Badge {
+                round_id,
+                kind: BadgeKind::Voter,
+                rank: None,
+                amount: QuadraticFunding::cal_amount(3, false).unwrap(),
+            })
+        );
+        assert_noop!(
+            QuadraticFunding::claim_voter_badge(Origin::signed(2), round_id),
+            Error::<Runtime>::NoBadgeToClaim
+        );
+        assert_noop!(
+            QuadraticFunding::claim_voter_badge(Origin::signed(4), round_id),
+            Error::<Runtime>::NoBadgeToClaim
+        );
+
+        // owners of funded projects claim theirs
+        assert_noop!(
+            QuadraticFunding::claim_project_badge(Origin::signed(2), round_id, first),
+            Error::<Runtime>::NotProjectOwner
+        );
+        assert_noop!(
+            QuadraticFunding::claim_project_badge(Origin::signed(2), round_id, second),
+            Error::<Runtime>::NoBadgeToClaim
+        );
+        assert_ok!(QuadraticFunding::claim_project_badge(
+            Origin::signed(1),
+            round_id,
+            first
+        ));
+        let project = QuadraticFunding::projects(round_id, first).unwrap();
+        assert_eq!(<Uniques as Inspect<u64>>::owner(&collection, &1), Some(1));
+        assert_eq!(QuadraticFunding::project_badges(round_id, first), Some(1));
+        assert_eq!(
+            QuadraticFunding::badge(1),
+            Some(Badge {
+                round_id,
+                kind: BadgeKind::Project(first),
+                rank: Some(1),
+                amount: project.grants + project.support_fund,
+            })
+        );
+        // the attributes are set on the item of the badge
+        assert_eq!(
+            <Uniques as Inspect<u64>>::attribute(&collection, &1, b"rank"),
+            Some(1u32.encode())
+        );
+        assert_eq!(
+            <Uniques as Inspect<u64>>::attribute(&collection, &0, b"rank"),
+            None
+        );
+        assert_noop!(
+            QuadraticFunding::claim_project_badge(Origin::signed(1), round_id, first),
+            Error::<Runtime>::BadgeAlreadyClaimed
+        );
+
+        // badges can not be transferred
+        assert_noop!(
+            Uniques::transfer(Origin::signed(2), collection, 0, 3),
+            pallet_uniques::Error::<Runtime>::Locked
+        );
+    })
+}
+
+#[test]
+fn test_voter_reserves_are_named() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let stake = 2 * ReserveUnit::get();
+        // reserved by another pallet
+        assert_ok!(Balances::reserve(&1, 5 * DOLLARS));
+        assert_ok!(QuadraticFunding::start_round(
+            Origin::root(),
+            round_id,
+            CurrencyId::DORA,
+            "doraRound".to_string().into(),
+            5,
+            2
+        ));
+        let project_hash = BlakeTwo256::hash_of(&1);
+        assert_ok!(QuadraticFunding::register_project(
+            Origin::signed(2),
+            round_id,
+            project_hash,
+            "project".to_string().into()
+        ));
+        for _ in 0..2 {
+            assert_ok!(QuadraticFunding::vote(
+                Origin::signed(1),
+                CurrencyId::DORA,
+                round_id,
+                project_hash,
+                1
+            ));
+        }
+        // the stake is reserved once per round
+        assert_eq!(Balances::reserved_balance_named(&RESERVE_ID, &1), stake);
+        assert_eq!(QuadraticFunding::round_reserves(round_id, 1), Some(stake));
+        assert_eq!(Balances::reserved_balance(&1), 5 * DOLLARS + stake);
+
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            round_id,
+            QuadraticFunding::round_witness(round_id)
+        ));
+        assert_eq!(Balances::reserved_balance_named(&RESERVE_ID, &1), 0);
+        assert_eq!(QuadraticFunding::round_reserves(round_id, 1), None);
+        assert_eq!(Balances::reserved_balance(&1), 5 * DOLLARS);
+    })
+}
+
+#[test]
+fn test_migrate_to_named_reserves() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let stake = 2 * ReserveUnit::get();
+        assert_ok!(QuadraticFunding::start_round(
+            Origin::root(),
+            round_id,
+            CurrencyId::DORA,
+            "doraRound".to_string().into(),
+            5,
+            2
+        ));
+        // the stake of a voter before reserves were named, besides another reserve
+        crate::RoundParticipants::<Runtime>::insert(round_id, 1, true);
+        assert_ok!(Balances::reserve(&1, stake + 5 * DOLLARS));
+        // a voter whose stake was partly slashed by another pallet
+        crate::RoundParticipants::<Runtime>::insert(round_id, 2, true);
+        assert_ok!(Balances::reserve(&2, stake / 2));
+
+        for _ in 0..2 {
+            MigrateToNamedReserves::<Runtime>::on_runtime_upgrade();
+            assert_eq!(Balances::reserved_balance_named(&RESERVE_ID, &1), stake);
+            assert_eq!(QuadraticFunding::round_reserves(round_id, 1), Some(stake));
+            assert_eq!(Balances::reserved_balance(&1), stake + 5 * DOLLARS);
+            assert_eq!(Balances::reserved_balance_named(&RESERVE_ID, &2), stake / 2);
+            assert_eq!(
+                QuadraticFunding::round_reserves(round_id, 2),
+                Some(stake / 2)
+            );
+            assert_eq!(Balances::reserved_balance(&2), stake / 2);
+        }
+
+        assert_ok!(QuadraticFunding::end_round(
+            Origin::root(),
+            round_id,
+            QuadraticFunding::round_witness(round_id)
+        ));
+        assert_eq!(Balances::reserved_balance(&1), 5 * DOLLARS);
+        assert_eq!(Balances::reserved_balance(&2), 0);
+    })
+}
+
+#[test]
+fn test_migrate_to_v1() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(0).put::<QuadraticFunding>();
+        let hash = BlakeTwo256::hash_of(&"project");
+        let pool = 10 * DOLLARS;
+        let stake = 2 * ReserveUnit::get();
+        // an ongoing round of the first release, its donations sent to the admin
+        v1::Rounds::<Runtime>::insert(
+            1,
+            v1::OldRound {
+                name: b"doraRound".to_vec().try_into().unwrap(),
+                currency_id: CurrencyId::DORA,
+                ongoing: true,
+                support_pool: pool,
+                pre_tax_support_pool: pool,
+                total_support_area: 0,
+                total_tax: 0,
+                round_reserve: 2,
+                admin: 3,
+            },
+        );
+        crate::Projects::<Runtime>::insert(
+            1,
+            hash,
+            crate::Project {
+                total_votes: 3,
+                grants: 0,
+                support_area: 0,
+                support_fund: 0,
+                name: b"project".to_vec().try_into().unwrap(),
+                owner: 2,
+            },
+        );
+        v1::ProjectVotes::<Runtime>::insert(BlakeTwo256::hash_of(&(&hash, &1u32)), 1, 3);
+        crate::RoundParticipants::<Runtime>::insert(1, 1, true);
+        assert_ok!(Balances::reserve(&1, stake));
+        // an ongoing round whose admin can not pay its pool anymore
+        v1::Rounds::<Runtime>::insert(
+            2,
+            v1::OldRound {
+                name: b"doraRound".to_vec().try_into().unwrap(),
+                currency_id: CurrencyId::DORA,
+                ongoing: true,
+                support_pool: pool,
+                pre_tax_support_pool: pool,
+                total_support_area: 0,
+                total_tax: 0,
+                round_reserve: 0,
+                admin: 4,
+            },
+        );
+        // an ended round, its projects are ranked for their badges
+        v1::Rounds::<Runtime>::insert(
+            3,
+            v1::OldRound {
+                name: b"doraRound".to_vec().try_into().unwrap(),
+                currency_id: CurrencyId::DORA,
+                ongoing: false,
+                support_pool: 0,
+                pre_tax_support_pool: 0,
+                total_support_area: 0,
+                total_tax: 0,
+                round_reserve: 0,
+                admin: 3,
+            },
+        );
+        for (name, grants) in [("first", 5 * DOLLARS), ("second", DOLLARS)] {
+            crate::Projects::<Runtime>::insert(
+                3,
+                BlakeTwo256::hash_of(&name),
+                crate::Project {
+                    total_votes: 0,
+                    grants,
+                    support_area: 0,
+                    support_fund: 0,
+                    name: name.as_bytes().to_vec().try_into().unwrap(),
+                    owner: 2,
+                },
+            );
+        }
+
+        for _ in 0..2 {
+            v1::MigrateToV1::<Runtime>::on_runtime_upgrade();
+            assert_eq!(QuadraticFunding::on_chain_storage_version(), 1);
+            let round = QuadraticFunding::rounds(1).unwrap();
+            assert_eq!(round.admin, 3);
+            assert_eq!(round.support_pool, pool);
+            assert_eq!(round.matching, MatchingMode::SupportArea);
+            assert_eq!(round.max_match_per_project, Perbill::one());
+            assert_eq!(round.cost_curve, CostCurve::default());
+            assert_eq!(crate::ProjectVotes::<Runtime>::get((1, hash, 1)), Some(3));
+            assert_eq!(QuadraticFunding::round_size(1).projects, 1);
+            assert_eq!(QuadraticFunding::round_size(1).participants, 1);
+            assert_eq!(QuadraticFunding::round_size(1).voters_per_project, 1);
+            assert_eq!(QuadraticFunding::round_size(1).votes, 1);
+            assert_eq!(QuadraticFunding::project_voters(1, hash), 1);
+            // the round pays from its own account now
+            assert_eq!(
+                Balances::free_balance(QuadraticFunding::round_account(1)),
+                pool
+            );
+            assert_eq!(Balances::free_balance(3), 100 * DOLLARS - pool);
+            assert_eq!(QuadraticFunding::round_holding(1, CurrencyId::DORA), pool);
+            assert_eq!(QuadraticFunding::round_held_value(1), pool);
+            assert_eq!(Balances::reserved_balance_named(&RESERVE_ID, &1), stake);
+            // the admin sponsored the pool it paid
+            assert_eq!(round.sponsors, 1);
+            assert_eq!(
+                QuadraticFunding::sponsorships(1, 3),
+                Some(crate::Sponsorship {
+                    amount: pool,
+                    conditions: Default::default(),
+                    void: false,
+                    refunded: false,
+                })
+            );
+            // the round whose pool could not be moved goes on without it
+            let round = QuadraticFunding::rounds(2).unwrap();
+            assert_eq!(round.support_pool, 0);
+            assert_eq!(round.pre_tax_support_pool, 0);
+            assert_eq!(round.sponsors, 0);
+            assert_eq!(QuadraticFunding::sponsorships(2, 4), None);
+            assert_eq!(
+                Balances::free_balance(QuadraticFunding::round_account(2)),
+                0
+            );
+            assert_eq!(QuadraticFunding::round_held_value(2), 0);
+            assert_eq!(
+                QuadraticFunding::project_rank(3, BlakeTwo256::hash_of(&"first")),
+                Some(1)
+            );
+            assert_eq!(
+                QuadraticFunding::project_rank(3, BlakeTwo256::hash_of(&"second")),
+                Some(2)
+            );
+        }
+    })
+}
+
+#[test]
+fn test_end_round_weight_scales_with_round_size() {
+    new_test_ext().execute_with(|| {
+        let round_id = 1;
+        let hash_a = BlakeTwo256::hash_of(&1);
+        let hash_b = BlakeTwo256::hash_of(&2);
+        assert_ok!(QuadraticFunding::start_round(
+            Origin::root(),
+            round_id,
+            CurrencyId::DORA,
+            "doraRound".to_string().into(),
+            1,
+            0
+        ));
+        let end_round = |witness| crate::Call::<Runtime>::end_round { round_id, witness };
+        assert_eq!(
+            end_round(QuadraticFunding::round_witness(round_id))
+                .get_dispatch_info()
+                .weight,
+            <() as WeightInfo>::end_round(0, 0, 0, 0) + <() as WeightInfo>::pairwise_matching(0)
+        );
+
+        assert_ok!(QuadraticFunding::register_project(
+            Origin::signed(1),
+            round_id,
+            hash_a,
+            "project a".to_string().into()
+        ));
+        assert_ok!(QuadraticFunding::register_project(
+            Origin::signed(2),
+            round_id,
+            hash_b,
+            "project b".to_string().into()
+        ));
+        for voter in [2, 3] {
+            for hash in [hash_a, hash_b] {
+                assert_ok!(QuadraticFunding::vote(
+                    Origin::signed(voter),
+                    CurrencyId::DORA,
+                    round_id,
+                    hash,
+                    1
+                ));
+            }
+        }
+        assert_ok!(QuadraticFunding::donate(
+            Origin::signed(1),
+            round_id,
+            DOLLARS,
+            CurrencyId::DORA
+        ));
+        // a voter is only counted once whatever the projects it voted for
+        let witness = QuadraticFunding::round_witness(round_id);
+        assert_eq!(
+            witness,
+            RoundWitness {
+                projects: 2,
+                votes: 4,
+                voters_per_project: 2,
+                participants: 2,
+                sponsors: 1,
+            }
+        );
+        // before dispatch, the round is weighed as if it used the pairwise matching
+        assert_eq!(
+            end_round(witness).get_dispatch_info().weight,
+            <() as WeightInfo>::end_round(2, 4, 2, 1) + <() as WeightInfo>::pairwise_matching(2)
+        );
+        assert!(
+            <() as WeightInfo>::end_round(2, 4, 2, 1) > <() as WeightInfo>::end_round(1, 1, 1, 1)
+        );
+        // whose projects never have more voters than `MaxVotersPerProject`
+        assert_eq!(
+            QuadraticFunding::end_round_weight(
+                &RoundWitness {
+                    voters_per_project: 1_000,
+                    ..witness
+                },
+                true
+            ),
+            <() as WeightInfo>::end_round(2, 4, 2, 1) + <() as WeightInfo>::pairwise_matching(198)
+        );
+
+        // the witness can not be smaller than the round
+        for smaller in [
+            RoundWitness {
+                projects: 1,
+                ..witness
+            },
+            RoundWitness {
+                votes: 3,
+                ..witness
+            },
+            RoundWitness {
+                voters_per_project: 1,
+                ..witness
+            },
+            RoundWitness {
+                participants: 1,
+                ..witness
+            },
+            RoundWitness {
+                sponsors: 0,
+                ..witness
+            },
+        ] {
+            assert_noop!(
+                QuadraticFunding::end_round(Origin::root(), round_id, smaller),
+                Error::<Runtime>::InvalidWitness
+            );
+        }
+        // the round is refunded what it did not use, its matching is not the pairwise one
+        let info = QuadraticFunding::end_round(
+            Origin::root(),
+            round_id,
+            RoundWitness {
+                projects: 10,
+                ..witness
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            info.actual_weight,
+            Some(<() as WeightInfo>::end_round(2, 4, 2, 1))
         );
     })
 }
@@ -15,6 +15,7 @@ use scale_info::TypeInfo;
 pub use frame_support::weights::{constants::WEIGHT_PER_SECOND, Weight, WeightToFeeCoefficient};
 
 pub mod currency;
+pub mod vesting;
 
 pub use crate::currency::CurrencyId;
 
@@ -75,6 +76,7 @@ pub enum ReserveIdentifier {
     Honzon,
     TransactionPayment,
     TransactionPaymentDeposit,
+    QuadraticFunding,
 
     // always the last, indicate number of variants
     Count,
@@ -0,0 +1,11 @@
+//! Linear vesting math shared by the pallets paying funds over time.
+
+use sp_runtime::traits::{AtLeast32BitUnsigned, Saturating};
+
+/// The part of `total` released after `elapsed` blocks of a schedule of `period` blocks
+pub fn linear_release<B: AtLeast32BitUnsigned + Copy>(total: B, elapsed: B, period: B) -> B {
+    if elapsed >= period {
+        return total;
+    }
+    total.saturating_mul(elapsed) / period
+}
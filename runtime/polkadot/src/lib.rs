@@ -9,18 +9,23 @@ include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 mod weights;
 
 pub mod xcm_config;
+#[cfg(test)]
+mod xcm_tests;
 
 pub mod constants;
 
 use cumulus_pallet_parachain_system::RelayNumberStrictlyIncreases;
 use smallvec::smallvec;
+use codec::{Decode, Encode, MaxEncodedLen};
 use sp_api::impl_runtime_apis;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
     create_runtime_str, generic, impl_opaque_keys,
-    traits::{AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, Convert},
+    traits::{
+        AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, Convert, Verify,
+    },
     transaction_validity::{TransactionSource, TransactionValidity},
-    ApplyExtrinsicResult,
+    ApplyExtrinsicResult, RuntimeDebug,
 };
 
 use sp_std::prelude::*;
@@ -31,8 +36,8 @@ use sp_version::RuntimeVersion;
 use frame_support::{
     construct_runtime, parameter_types,
     traits::{
-        ConstBool, ConstU32, Contains, Currency, EqualPrivilegeOnly, Everything, Imbalance,
-        OnUnbalanced,
+        AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU32, Contains, Currency,
+        EqualPrivilegeOnly, Everything, Imbalance, InstanceFilter, NeverEnsureOrigin, OnUnbalanced,
     },
     weights::{
         constants::WEIGHT_PER_SECOND, ConstantMultiplier, DispatchClass, Weight,
@@ -101,6 +106,13 @@ pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signatu
 /// Extrinsic type that has already been checked.
 pub type CheckedExtrinsic = generic::CheckedExtrinsic<AccountId, Call, SignedExtra>;
 
+/// Migrations of the storage run on the next runtime upgrade, each one does nothing once the
+/// on-chain version of its pallet is up to date.
+pub type Migrations = (
+    pallet_qf::migrations::v1::MigrateToV1<Runtime>,
+    pallet_dora_rewards::migrations::v1::MigrateToV1<Runtime>,
+);
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
     Runtime,
@@ -108,6 +120,7 @@ pub type Executive = frame_executive::Executive<
     frame_system::ChainContext<Runtime>,
     Runtime,
     AllPalletsWithSystem,
+    Migrations,
 >;
 
 /// Opaque types. These are used by the CLI to instantiate machinery that don't need to know
@@ -138,7 +151,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("DORA DOT Parachain"),
     impl_name: create_runtime_str!("DORA DOT Parachain"),
     authoring_version: 1,
-    spec_version: 1,
+    spec_version: 2,
     impl_version: 0,
     apis: RUNTIME_API_VERSIONS,
     transaction_version: 1,
@@ -511,6 +524,29 @@ impl pallet_preimage::Config for Runtime {
     type ByteDeposit = PreimageByteDeposit;
 }
 
+impl pallet_uniques::Config for Runtime {
+    type Event = Event;
+    type CollectionId = u32;
+    type ItemId = u32;
+    type Currency = Balances;
+    type ForceOrigin = EnsureRoot<AccountId>;
+    // only the participation badges of the rounds are minted for now
+    type CreateOrigin = AsEnsureOriginWithArg<NeverEnsureOrigin<AccountId>>;
+    // badges can not be transferred
+    type Locker = QuadraticFunding;
+    type CollectionDeposit = ConstU128<0>;
+    type ItemDeposit = ConstU128<0>;
+    type MetadataDepositBase = ConstU128<0>;
+    type AttributeDepositBase = ConstU128<0>;
+    type DepositPerByte = ConstU128<0>;
+    type StringLimit = ConstU32<128>;
+    type KeyLimit = ConstU32<32>;
+    type ValueLimit = ConstU32<64>;
+    type WeightInfo = pallet_uniques::weights::SubstrateWeight<Runtime>;
+    #[cfg(feature = "runtime-benchmarks")]
+    type Helper = ();
+}
+
 impl pallet_utility::Config for Runtime {
     type Event = Event;
     type Call = Call;
@@ -532,6 +568,24 @@ parameter_types! {
     pub const AppId: u8 = 1;
     // minimal number of units to reserve to get qualified to vote
     pub const ReserveUnit: u128 = 1000000000000;
+    pub const MaxSponsors: u32 = 100;
+    // what a sponsor pays at least to take one of the `MaxSponsors` slots of a round
+    pub const MinSponsorship: Balance = DOLLARS;
+    pub const MaxMilestones: u32 = 10;
+    // Funds of projects with milestones are returned to the treasury after this period
+    pub const MilestoneDeadline: BlockNumber = 180 * DAYS;
+    // Reserved by a challenger flagging a project
+    pub const ChallengeBond: Balance = 10 * DOLLARS;
+    pub const ArchiveDelay: BlockNumber = 30 * DAYS;
+    pub const MaxVotedProjects: u32 = 100;
+    // bounds the pairs of voters the pairwise matching goes over at the end of its rounds
+    pub const MaxVotersPerProject: u32 = 64;
+    // bounds the projects going through the matching at the end of a round
+    pub const MaxProjectsPerRound: u32 = 100;
+    // bounds the votes and voters going through the matching at the end of a round
+    pub const MaxVotesPerRound: u32 = 640;
+    pub const MaxRoundCurrencies: u32 = 4;
+    pub const BadgeCollection: u32 = 0;
 }
 
 /// Configure the pallet-qf in pallets/quadratic-funding.
@@ -542,6 +596,9 @@ impl pallet_qf::Config for Runtime {
     type PalletId = QuadraticFundingPalletId;
     // Origin who can control the round
     type AdminOrigin = EnsureRoot<AccountId>;
+    // Origin moving treasury funds into a round
+    type TreasuryOrigin = EnsureRoot<AccountId>;
+    type TreasuryAccount = TreasuryAccount;
     // Use the UnitOfVote from the parameter_types block.
     type UnitOfVote = VoteUnit;
     // Use the MinNickLength from the parameter_types block.
@@ -553,6 +610,32 @@ impl pallet_qf::Config for Runtime {
     // The maximum length of project name
     type NameMaxLength = NameMaxLength;
     type ReserveUnit = ReserveUnit;
+    type MaxSponsors = MaxSponsors;
+    type MinSponsorship = MinSponsorship;
+    // Origin approving milestones, besides the round admin
+    type MilestoneReviewerOrigin = EnsureRoot<AccountId>;
+    type MaxMilestones = MaxMilestones;
+    type MilestoneDeadline = MilestoneDeadline;
+    // Origin flagging projects without a bond
+    type ChallengeOrigin = EnsureRoot<AccountId>;
+    // Origin resolving the flags
+    type ResolutionOrigin = EnsureRoot<AccountId>;
+    type ChallengeBond = ChallengeBond;
+    type ArchiveDelay = ArchiveDelay;
+    type MaxVotedProjects = MaxVotedProjects;
+    type MaxVotersPerProject = MaxVotersPerProject;
+    type MaxProjectsPerRound = MaxProjectsPerRound;
+    type MaxVotesPerRound = MaxVotesPerRound;
+    // no oracle yet, other currencies are accepted at a fixed rate
+    type PriceFeed = pallet_qf::NoPriceFeed;
+    type MaxRoundCurrencies = MaxRoundCurrencies;
+    type OffchainSignature = Signature;
+    type OffchainPublic = <Signature as Verify>::Signer;
+    type OnRoundStarted = ();
+    type OnVote = ();
+    type OnRoundEnded = ();
+    type Badges = pallet_qf::badges::UniquesBadges<Runtime>;
+    type BadgeCollection = BadgeCollection;
     type WeightInfo = pallet_qf::weights::DoraWeight<Runtime>;
 }
 
@@ -649,6 +732,77 @@ impl orml_unknown_tokens::Config for Runtime {
     type Event = Event;
 }
 
+parameter_types! {
+    // One storage item; key size 32, value size 8.
+    pub ProxyDepositBase: Balance = deposit(1, 8);
+    // Additional storage item size of 33 bytes.
+    pub ProxyDepositFactor: Balance = deposit(0, 33);
+    // One storage item; key size 32, value size 16.
+    pub AnnouncementDepositBase: Balance = deposit(1, 16);
+    pub AnnouncementDepositFactor: Balance = deposit(0, 68);
+}
+
+/// The type used to represent the kinds of proxying allowed.
+#[derive(
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Encode,
+    Decode,
+    RuntimeDebug,
+    MaxEncodedLen,
+    scale_info::TypeInfo,
+)]
+pub enum ProxyType {
+    /// Every call
+    Any,
+    /// Quadratic funding calls only, to vote or manage rounds on behalf of the proxied account
+    QuadraticFunding,
+}
+
+impl Default for ProxyType {
+    fn default() -> Self {
+        Self::Any
+    }
+}
+
+impl InstanceFilter<Call> for ProxyType {
+    fn filter(&self, c: &Call) -> bool {
+        match self {
+            ProxyType::Any => true,
+            ProxyType::QuadraticFunding => {
+                matches!(c, Call::QuadraticFunding(..) | Call::Utility(..))
+            }
+        }
+    }
+
+    fn is_superset(&self, o: &Self) -> bool {
+        match (self, o) {
+            (x, y) if x == y => true,
+            (ProxyType::Any, _) => true,
+            _ => false,
+        }
+    }
+}
+
+impl pallet_proxy::Config for Runtime {
+    type Event = Event;
+    type Call = Call;
+    type Currency = Balances;
+    type ProxyType = ProxyType;
+    type ProxyDepositBase = ProxyDepositBase;
+    type ProxyDepositFactor = ProxyDepositFactor;
+    type MaxProxies = ConstU32<32>;
+    type WeightInfo = pallet_proxy::weights::SubstrateWeight<Runtime>;
+    type MaxPending = ConstU32<32>;
+    type CallHasher = BlakeTwo256;
+    type AnnouncementDepositBase = AnnouncementDepositBase;
+    type AnnouncementDepositFactor = AnnouncementDepositFactor;
+}
+
 // Create the runtime by composing the FRAME pallets that were previously configured.
 construct_runtime!(
     pub enum Runtime where
@@ -666,6 +820,7 @@ construct_runtime!(
         ParachainInfo: parachain_info::{Pallet, Storage, Config} = 4,
         Scheduler: pallet_scheduler::{Pallet, Call, Storage, Event<T>} = 5,
         Preimage: pallet_preimage::{Pallet, Call, Storage, Event<T>} = 6,
+        Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>} = 8,
 
         // Monetary stuff.
         Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>} = 10,
@@ -695,6 +850,7 @@ construct_runtime!(
         QuadraticFunding: pallet_qf::{Pallet, Call, Storage, Event<T>} = 50,
         DaoCoreModule: dao_core::{Pallet, Call, Storage, Event<T>} = 51,
         DoraRewards: pallet_dora_rewards::{Pallet, Call, Storage, Event<T>, Config<T>} = 52,
+        Uniques: pallet_uniques::{Pallet, Call, Storage, Event<T>} = 53,
 
         // Sudo
         Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>} = 255,
@@ -818,6 +974,16 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_qf_runtime_api::QuadraticFundingApi<Block, AccountId, Hash> for Runtime {
+        fn voter_participation(round_id: u32, who: AccountId) -> Option<(Vec<(Hash, u128)>, u128)> {
+            QuadraticFunding::participation_in_round(round_id, who)
+        }
+
+        fn voter_history(who: AccountId, from_round: u32, count: u32) -> Vec<(u32, Vec<(Hash, u128)>, u128)> {
+            QuadraticFunding::voter_history(who, from_round, count)
+        }
+    }
+
     impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
         fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
             ParachainSystem::collect_collation_info(header)
@@ -929,3 +1095,27 @@ cumulus_pallet_parachain_system::register_validate_block! {
     BlockExecutor = cumulus_pallet_aura_ext::BlockExecutor::<Runtime, Executive>,
     CheckInherents = CheckInherents,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ending_the_largest_round_fits_in_a_block() {
+        let votes = MaxVotesPerRound::get();
+        let witness = pallet_qf::RoundWitness {
+            projects: MaxProjectsPerRound::get(),
+            votes,
+            voters_per_project: MaxVotersPerProject::get(),
+            // every participant voted at least once
+            participants: votes,
+            // the treasury sponsors besides the `MaxSponsors`
+            sponsors: MaxSponsors::get() + 1,
+        };
+        let max_extrinsic = RuntimeBlockWeights::get()
+            .get(DispatchClass::Normal)
+            .max_extrinsic
+            .unwrap();
+        assert!(QuadraticFunding::end_round_weight(&witness, true) <= max_extrinsic);
+    }
+}
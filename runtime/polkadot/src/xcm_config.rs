@@ -3,9 +3,10 @@ use super::{
     ParachainInfo, ParachainSystem, PolkadotXcm, Runtime, ToTreasury, TreasuryAccount,
     UnknownTokens, Vec, XcmpQueue, MAXIMUM_BLOCK_WEIGHT,
 };
+use codec::{Decode, Encode};
 use frame_support::{
     match_types, parameter_types,
-    traits::{Everything, Nothing},
+    traits::{Contains, Everything, Nothing},
     weights::Weight,
 };
 use orml_traits::{location::AbsoluteReserveProvider, parameter_type_with_key, MultiCurrency};
@@ -14,14 +15,19 @@ use orml_xcm_support::{
 };
 use pallet_xcm::XcmPassthrough;
 use polkadot_parachain::primitives::Sibling;
-use xcm::latest::prelude::*;
+use sp_core::hashing::blake2_256;
+use sp_std::{borrow::Borrow, marker::PhantomData};
+use xcm::{latest::prelude::*, DoubleEncoded};
 use xcm_builder::{
     AccountId32Aliases, AllowTopLevelPaidExecutionFrom, AllowUnpaidExecutionFrom, EnsureXcmOrigin,
     FixedRateOfFungible, FixedWeightBounds, LocationInverter, ParentIsPreset, RelayChainAsNative,
     SiblingParachainAsNative, SiblingParachainConvertsVia, SignedAccountId32AsNative,
     SignedToAccountId32, SovereignSignedViaLocation, TakeRevenue, TakeWeightCredit,
 };
-use xcm_executor::XcmExecutor;
+use xcm_executor::{
+    traits::{Convert as XcmConvert, ShouldExecute},
+    XcmExecutor,
+};
 
 parameter_types! {
     pub const RelayLocation: MultiLocation = MultiLocation::parent();
@@ -40,8 +46,32 @@ pub type LocationToAccountId = (
     SiblingParachainConvertsVia<Sibling, AccountId>,
     // Straight up local `AccountId32` origins just alias directly to `AccountId`.
     AccountId32Aliases<RelayNetwork, AccountId>,
+    // Accounts of sibling parachains get an account derived from their location.
+    SiblingAccount32Hash<AccountId>,
 );
 
+/// Derives a local account from the location of an account of a sibling parachain,
+/// `(1, Parachain(id), AccountId32)`, so that its users can take part in the rounds without
+/// bridging first.
+pub struct SiblingAccount32Hash<AccountId>(PhantomData<AccountId>);
+impl<AccountId: From<[u8; 32]> + Clone> XcmConvert<MultiLocation, AccountId>
+    for SiblingAccount32Hash<AccountId>
+{
+    fn convert_ref(location: impl Borrow<MultiLocation>) -> Result<AccountId, ()> {
+        match location.borrow() {
+            location @ MultiLocation {
+                parents: 1,
+                interior: X2(Parachain(_), AccountId32 { .. }),
+            } => Ok(("multiloc", location).using_encoded(blake2_256).into()),
+            _ => Err(()),
+        }
+    }
+
+    fn reverse(who: AccountId) -> Result<MultiLocation, AccountId> {
+        Err(who)
+    }
+}
+
 /// This is the type we use to convert an (incoming) XCM origin into a local `Origin` instance,
 /// ready for dispatching a transaction with Xcm's `Transact`. There is an `OriginKind` which can
 /// biases the kind of local `Origin` it will become.
@@ -82,12 +112,61 @@ match_types! {
     };
 }
 
+/// The calls sibling parachains can `Transact`, they vote and donate on behalf of their users.
+pub struct SiblingTransactCalls;
+impl Contains<Call> for SiblingTransactCalls {
+    fn contains(call: &Call) -> bool {
+        matches!(
+            call,
+            Call::QuadraticFunding(pallet_qf::Call::vote { .. } | pallet_qf::Call::donate { .. })
+        )
+    }
+}
+
+/// Rejects the messages of sibling parachains with a `Transact` of a call not in `Calls`, the
+/// other messages are left to `Inner`.
+pub struct FilterSiblingTransact<Calls, Inner>(PhantomData<(Calls, Inner)>);
+impl<Calls: Contains<Call>, Inner: ShouldExecute> ShouldExecute
+    for FilterSiblingTransact<Calls, Inner>
+{
+    fn should_execute<RuntimeCall>(
+        origin: &MultiLocation,
+        message: &mut Xcm<RuntimeCall>,
+        max_weight: Weight,
+        weight_credit: &mut Weight,
+    ) -> Result<(), ()> {
+        let from_sibling =
+            origin.parents == 1 && matches!(origin.first_interior(), Some(Parachain(_)));
+        if from_sibling && !transacts_allowed::<Calls, _>(message) {
+            return Err(());
+        }
+        Inner::should_execute(origin, message, max_weight, weight_credit)
+    }
+}
+
+/// Whether every `Transact` of `message`, including the nested ones, dispatches a call in `Calls`
+fn transacts_allowed<Calls: Contains<Call>, RuntimeCall>(message: &Xcm<RuntimeCall>) -> bool {
+    message.0.iter().all(|instruction| match instruction {
+        Transact { call, .. } => DoubleEncoded::<Call>::decode(&mut &call.encode()[..])
+            .ok()
+            .and_then(|mut call| call.take_decoded().ok())
+            .map_or(false, |call| Calls::contains(&call)),
+        SetErrorHandler(xcm) | SetAppendix(xcm) => transacts_allowed::<Calls, _>(xcm),
+        _ => true,
+    })
+}
+
 pub type Barrier = (
     TakeWeightCredit,
-    AllowTopLevelPaidExecutionFrom<Everything>,
-    AllowUnpaidExecutionFrom<ParentOrParentsExecutivePlurality>,
-    // ^^^ Parent and its exec plurality get free execution
-    AllowUnpaidExecutionFrom<SpecParachain>,
+    FilterSiblingTransact<
+        SiblingTransactCalls,
+        (
+            AllowTopLevelPaidExecutionFrom<Everything>,
+            AllowUnpaidExecutionFrom<ParentOrParentsExecutivePlurality>,
+            // ^^^ Parent and its exec plurality get free execution
+            AllowUnpaidExecutionFrom<SpecParachain>,
+        ),
+    >,
 );
 
 pub type LocalAssetTransactor = MultiCurrencyAdapter<
@@ -0,0 +1,261 @@
+//! Users of sibling parachains taking part in the rounds through XCM `Transact`.
+
+mod relay_chain;
+
+use crate::{
+    xcm_config::LocationToAccountId, AccountId, Balances, Call, CurrencyId, DmpQueue, Origin,
+    QuadraticFunding, Runtime, System, XcmpQueue, DOLLARS,
+};
+use codec::Encode;
+use cumulus_primitives_core::XcmpMessageFormat;
+use frame_support::{assert_ok, traits::GenesisBuild, weights::Weight};
+use sp_core::H256;
+use xcm::{latest::prelude::*, VersionedXcm};
+use xcm_executor::traits::Convert as XcmConvert;
+use xcm_simulator::{
+    decl_test_network, decl_test_parachain, decl_test_relay_chain, TestExt, TestExternalities,
+    XcmpMessageHandlerT,
+};
+
+const DORA_ID: u32 = 2087;
+const SIBLING_ID: u32 = 2000;
+const ALICE: [u8; 32] = [1u8; 32];
+const BOB: [u8; 32] = [2u8; 32];
+const TRANSACT_WEIGHT: Weight = 2_000_000_000;
+
+decl_test_parachain! {
+    pub struct Dora {
+        Runtime = Runtime,
+        XcmpMessageHandler = XcmpQueue,
+        DmpMessageHandler = DmpQueue,
+        new_ext = dora_ext(),
+    }
+}
+
+decl_test_relay_chain! {
+    pub struct Relay {
+        Runtime = relay_chain::Runtime,
+        XcmConfig = relay_chain::XcmConfig,
+        new_ext = relay_ext(),
+    }
+}
+
+decl_test_network! {
+    pub struct MockNet {
+        relay_chain = Relay,
+        parachains = vec![
+            (2087, Dora),
+        ],
+    }
+}
+
+/// The account of the sibling parachain on Dora
+fn sibling_account() -> AccountId {
+    LocationToAccountId::convert_ref(MultiLocation::new(1, X1(Parachain(SIBLING_ID)))).unwrap()
+}
+
+/// Alice of the sibling parachain, as seen from Dora
+fn alice_location() -> MultiLocation {
+    MultiLocation::new(
+        1,
+        X2(
+            Parachain(SIBLING_ID),
+            AccountId32 {
+                network: NetworkId::Any,
+                id: ALICE,
+            },
+        ),
+    )
+}
+
+/// The account derived for Alice on Dora
+fn alice_account() -> AccountId {
+    LocationToAccountId::convert_ref(alice_location()).unwrap()
+}
+
+fn dora() -> MultiLocation {
+    MultiLocation::new(
+        0,
+        X1(GeneralKey(
+            b"DORA"
+                .to_vec()
+                .try_into()
+                .expect("less than length limit; qed"),
+        )),
+    )
+}
+
+fn dora_ext() -> TestExternalities {
+    let mut t = frame_system::GenesisConfig::default()
+        .build_storage::<Runtime>()
+        .unwrap();
+    pallet_balances::GenesisConfig::<Runtime> {
+        balances: vec![
+            (sibling_account(), 1_000 * DOLLARS),
+            (BOB.into(), 1_000 * DOLLARS),
+        ],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    <parachain_info::GenesisConfig as GenesisBuild<Runtime>>::assimilate_storage(
+        &parachain_info::GenesisConfig {
+            parachain_id: DORA_ID.into(),
+        },
+        &mut t,
+    )
+    .unwrap();
+    let mut ext = TestExternalities::new(t);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+fn relay_ext() -> TestExternalities {
+    let t = frame_system::GenesisConfig::default()
+        .build_storage::<relay_chain::Runtime>()
+        .unwrap();
+    let mut ext = TestExternalities::new(t);
+    ext.execute_with(|| relay_chain::System::set_block_number(1));
+    ext
+}
+
+/// Deliver a message sent by the sibling parachain to Dora
+fn send_from_sibling(message: Xcm<()>) {
+    let encoded = (
+        XcmpMessageFormat::ConcatenatedVersionedXcm,
+        VersionedXcm::from(message),
+    )
+        .encode();
+    Dora::handle_xcmp_messages(
+        vec![(SIBLING_ID.into(), 1, &encoded[..])].into_iter(),
+        Weight::MAX,
+    );
+}
+
+/// Start round 1 with a project of Bob
+fn start_round() -> H256 {
+    let hash = H256::repeat_byte(1);
+    Dora::execute_with(|| {
+        assert_ok!(QuadraticFunding::start_round(
+            Origin::root(),
+            1,
+            CurrencyId::DORA,
+            b"round".to_vec(),
+            BOB.into(),
+            0
+        ));
+        assert_ok!(QuadraticFunding::register_project(
+            Origin::signed(BOB.into()),
+            1,
+            hash,
+            b"project".to_vec()
+        ));
+    });
+    hash
+}
+
+/// Fund the account of the origin with DORA of the sibling, then transact `call` as the origin
+fn transact(descend: Option<Junction>, call: Call) -> Xcm<()> {
+    let amount = 10 * DOLLARS;
+    let mut instructions = vec![
+        WithdrawAsset((dora(), amount).into()),
+        BuyExecution {
+            fees: (dora(), amount).into(),
+            weight_limit: Unlimited,
+        },
+    ];
+    let beneficiary = match descend {
+        Some(junction) => {
+            instructions.push(DescendOrigin(X1(junction.clone())));
+            MultiLocation::new(1, X2(Parachain(SIBLING_ID), junction))
+        }
+        None => MultiLocation::new(1, X1(Parachain(SIBLING_ID))),
+    };
+    instructions.push(DepositAsset {
+        assets: All.into(),
+        max_assets: 1,
+        beneficiary,
+    });
+    instructions.push(Transact {
+        origin_type: OriginKind::SovereignAccount,
+        require_weight_at_most: TRANSACT_WEIGHT,
+        call: call.encode().into(),
+    });
+    Xcm(instructions)
+}
+
+#[test]
+fn sibling_user_votes_through_transact() {
+    MockNet::reset();
+    let hash = start_round();
+    let vote = Call::QuadraticFunding(pallet_qf::Call::vote {
+        currency_id: CurrencyId::DORA,
+        round_id: 1,
+        hash,
+        ballot: 2,
+    });
+    send_from_sibling(transact(
+        Some(AccountId32 {
+            network: NetworkId::Any,
+            id: ALICE,
+        }),
+        vote,
+    ));
+    Dora::execute_with(|| {
+        // the vote is cast by the account derived for Alice, paid with what was deposited
+        let participation = QuadraticFunding::voter_participation(1, alice_account()).unwrap();
+        assert_eq!(participation.projects.into_inner(), vec![hash]);
+        assert_eq!(participation.spent, QuadraticFunding::cal_amount(3, false).unwrap());
+        assert_eq!(QuadraticFunding::projects(1, hash).unwrap().total_votes, 2);
+        assert!(Balances::free_balance(&alice_account()) > 0);
+        assert_eq!(Balances::free_balance(&sibling_account()), 990 * DOLLARS);
+    });
+}
+
+#[test]
+fn sibling_donates_through_transact() {
+    MockNet::reset();
+    start_round();
+    let donate = Call::QuadraticFunding(pallet_qf::Call::donate {
+        round_id: 1,
+        amount: 2 * DOLLARS,
+        currency_id: CurrencyId::DORA,
+    });
+    send_from_sibling(transact(None, donate));
+    Dora::execute_with(|| {
+        // the sovereign account of the sibling sponsors the round
+        assert!(QuadraticFunding::sponsorships(1, sibling_account()).is_some());
+        assert!(QuadraticFunding::rounds(1).unwrap().support_pool > 0);
+    });
+}
+
+#[test]
+fn sibling_can_not_transact_other_calls() {
+    MockNet::reset();
+    start_round();
+    let end_round = Call::QuadraticFunding(pallet_qf::Call::end_round {
+        round_id: 1,
+        witness: Default::default(),
+    });
+    send_from_sibling(transact(None, end_round));
+    let remark = Call::System(frame_system::Call::remark {
+        remark: b"hello".to_vec(),
+    });
+    // a forbidden call can not be hidden in the appendix either
+    send_from_sibling(Xcm(vec![
+        WithdrawAsset((dora(), 10 * DOLLARS).into()),
+        BuyExecution {
+            fees: (dora(), 10 * DOLLARS).into(),
+            weight_limit: Unlimited,
+        },
+        SetAppendix(Xcm(vec![Transact {
+            origin_type: OriginKind::SovereignAccount,
+            require_weight_at_most: TRANSACT_WEIGHT,
+            call: remark.encode().into(),
+        }])),
+    ]));
+    Dora::execute_with(|| {
+        // the messages are rejected before anything is withdrawn
+        assert!(QuadraticFunding::rounds(1).unwrap().ongoing);
+        assert_eq!(Balances::free_balance(&sibling_account()), 1_000 * DOLLARS);
+    });
+}